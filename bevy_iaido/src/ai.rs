@@ -1,17 +1,49 @@
+use serde::{Deserialize, Serialize};
+
 use crate::combat::correct_direction_for;
+use crate::logging::MatchLog;
 use crate::rng::XorShift32;
-use crate::types::{Direction, Opening};
+use crate::types::{Direction, Opening, SurvivalSchedule, SwipeEvent};
 
 #[derive(Copy, Clone, Debug)]
 pub struct AiProfile {
     pub mean_reaction_ms: u64,
     pub wrong_percent: u8, // 0..=100
+    pub struggle_tap_ms: u64, // mean interval between taps in a sword-lock
+    pub rating: i32, // fixed Elo the player's rating is measured against
+}
+
+pub const DUMB: AiProfile = AiProfile { mean_reaction_ms: 500, wrong_percent: 30, struggle_tap_ms: 260, rating: 800 };
+pub const NOVICE: AiProfile = AiProfile { mean_reaction_ms: 450, wrong_percent: 20, struggle_tap_ms: 200, rating: 1000 };
+pub const SKILLED: AiProfile = AiProfile { mean_reaction_ms: 350, wrong_percent: 10, struggle_tap_ms: 150, rating: 1300 };
+pub const MASTER: AiProfile = AiProfile { mean_reaction_ms: 140, wrong_percent: 0, struggle_tap_ms: 110, rating: 1700 };
+
+/// Named difficulty, for settings and challenge rulesets that need to serialise it.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AiTier {
+    #[default]
+    Dumb,
+    Novice,
+    Skilled,
+    Master,
 }
 
-pub const DUMB: AiProfile = AiProfile { mean_reaction_ms: 500, wrong_percent: 30 };
-pub const NOVICE: AiProfile = AiProfile { mean_reaction_ms: 450, wrong_percent: 20 };
-pub const SKILLED: AiProfile = AiProfile { mean_reaction_ms: 350, wrong_percent: 10 };
-pub const MASTER: AiProfile = AiProfile { mean_reaction_ms: 140, wrong_percent: 0 };
+impl AiTier {
+    pub fn profile(self) -> AiProfile {
+        match self { AiTier::Dumb => DUMB, AiTier::Novice => NOVICE, AiTier::Skilled => SKILLED, AiTier::Master => MASTER }
+    }
+}
+
+impl std::str::FromStr for AiTier {
+    type Err = String;
+
+    // Variant name in any case, e.g. "master"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [AiTier::Dumb, AiTier::Novice, AiTier::Skilled, AiTier::Master].into_iter()
+            .find(|t| format!("{:?}", t).eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("unknown AI tier: {}", s))
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct AiPlan {
@@ -50,3 +82,69 @@ pub fn plan_for_go(profile: AiProfile, rng: &mut XorShift32) -> AiPlan {
     let wrong = (rng.next_u32() % 100) < profile.wrong_percent as u32;
     AiPlan { reaction_ms, wrong }
 }
+
+// Follow-up combo steps: the sequence is already known, so react faster than to GO
+pub fn plan_for_step(profile: AiProfile, rng: &mut XorShift32) -> AiPlan {
+    let mut plan = plan_for_go(profile, rng);
+    plan.reaction_ms = (plan.reaction_ms * 3 / 5).max(60);
+    plan
+}
+
+// Delay until the next struggle tap: ±25% jitter around the profile's tap interval
+pub fn plan_struggle_tap(profile: AiProfile, rng: &mut XorShift32) -> u64 {
+    let spread = profile.struggle_tap_ms / 2;
+    let jitter = rng.range_u64(0, spread) as i64 - (spread / 2) as i64;
+    (profile.struggle_tap_ms as i64 + jitter).max(40) as u64
+}
+
+/// Profile for survival round `round`: reactions sped up per the schedule.
+pub fn survival_profile(profile: AiProfile, schedule: &SurvivalSchedule, round: u32) -> AiProfile {
+    AiProfile { mean_reaction_ms: schedule.ai_reaction_at(profile.mean_reaction_ms, round), ..profile }
+}
+
+/// Opponent that re-runs the human side of a recorded match instead of rolling
+/// reactions from a profile. Same seed, same openings: round `i` of the live match
+/// is answered with round `i` of the log, right or wrong as it was recorded.
+#[derive(Clone, Debug)]
+pub struct Ghost {
+    pub log: MatchLog,
+}
+
+impl Ghost {
+    pub fn new(log: MatchLog) -> Self { Self { log } }
+
+    /// Plan for combo step `step` of round `round` (step 0 for single-swipe rounds),
+    /// timed from GO/the cut cue or the previous step. `None` when the recorded run had
+    /// no swipe there, or only a false start: the ghost then holds still.
+    pub fn plan(&self, round: usize, step: usize) -> Option<AiPlan> {
+        let r = self.log.rounds.get(round)?;
+        let origin = r.cut_ts_ms.unwrap_or(r.go.ts_ms);
+        let (ev, opening, prev_ts) = if r.human_combo.is_empty() {
+            if step > 0 { return None; }
+            (r.human.as_ref()?, r.human_opening, origin)
+        } else {
+            let prev = if step == 0 { origin } else { r.human_steps.get(step - 1)?.ts_ms };
+            (r.human_steps.get(step)?, r.human_combo[step], prev)
+        };
+        ghost_plan(ev, opening, prev_ts)
+    }
+
+    /// Draw timing for draw-then-cut rounds; any direction draws.
+    pub fn plan_draw(&self, round: usize) -> Option<AiPlan> {
+        let r = self.log.rounds.get(round)?;
+        let draw = r.human_draw.as_ref()?;
+        Some(AiPlan { reaction_ms: draw.ts_ms.checked_sub(r.go.ts_ms)?, wrong: false })
+    }
+
+    /// Recorded human reaction for round `round`, for side-by-side display.
+    pub fn reaction_ms(&self, round: usize) -> Option<u64> {
+        let r = self.log.rounds.get(round)?;
+        let last = if r.human_combo.is_empty() { r.human.as_ref() } else { r.human_steps.last() }?;
+        last.ts_ms.checked_sub(r.cut_ts_ms.unwrap_or(r.go.ts_ms))
+    }
+}
+
+fn ghost_plan(ev: &SwipeEvent, opening: Opening, prev_ts: u64) -> Option<AiPlan> {
+    let reaction_ms = ev.ts_ms.checked_sub(prev_ts)?;
+    Some(AiPlan { reaction_ms, wrong: ev.dir != correct_direction_for(opening) })
+}
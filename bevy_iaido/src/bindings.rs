@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+
+/// What a key, pad button or on-screen button does, whatever the device.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputAction {
+    // Duel: the human side, and each pad's side
+    Up,
+    Down,
+    Left,
+    Right,
+    // Duel: player two in hot-seat; otherwise more keys for the human side
+    P2Up,
+    P2Down,
+    P2Left,
+    P2Right,
+    // Playground: walk, or step frames in edit mode; MoveLeft/MoveDown pick the block
+    MoveLeft,
+    MoveRight,
+    MoveDown,
+    Slash,
+    HeavySlash,
+    Spin,
+    Block,
+    /// Dash; sets the slash frame in edit mode.
+    Dash,
+    /// Sets the clash frame in edit mode.
+    SetClash,
+    SaveController,
+    // Anywhere
+    ToggleEdit,
+    ToggleTouch,
+}
+
+/// Where an action is read. Actions in one scope, or a global one and any other, must not share an input.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum InputScope {
+    Duel,
+    Playground,
+    Global,
+}
+
+impl InputAction {
+    pub const ALL: [InputAction; 20] = [
+        Self::Up, Self::Down, Self::Left, Self::Right,
+        Self::P2Up, Self::P2Down, Self::P2Left, Self::P2Right,
+        Self::MoveLeft, Self::MoveRight, Self::MoveDown,
+        Self::Slash, Self::HeavySlash, Self::Spin, Self::Block, Self::Dash, Self::SetClash, Self::SaveController,
+        Self::ToggleEdit, Self::ToggleTouch,
+    ];
+
+    pub fn scope(self) -> InputScope {
+        use InputAction::*;
+        match self {
+            Up | Down | Left | Right | P2Up | P2Down | P2Left | P2Right => InputScope::Duel,
+            ToggleEdit | ToggleTouch => InputScope::Global,
+            _ => InputScope::Playground,
+        }
+    }
+}
+
+/// Inputs bound to one action, by name: Bevy `KeyCode` and `GamepadButtonType` variants
+/// ("KeyW", "ArrowUp", "DPadUp", "South") and on-screen buttons (`VirtualKey`: "Up", "Z", "Space").
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Binding {
+    pub action: InputAction,
+    #[serde(default)]
+    pub keys: Vec<String>,
+    #[serde(default)]
+    pub pad: Vec<String>,
+    #[serde(default)]
+    pub touch: Vec<String>,
+}
+
+impl Binding {
+    fn new(action: InputAction, keys: &[&str], pad: &[&str], touch: &[&str]) -> Self {
+        let names = |n: &[&str]| n.iter().map(|s| s.to_string()).collect();
+        Self { action, keys: names(keys), pad: names(pad), touch: names(touch) }
+    }
+}
+
+/// Controls, read from `INPUT_MAP_FILE`. Actions the file leaves out keep their default binding.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputBindings {
+    pub bindings: Vec<Binding>,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        use InputAction::*;
+        Self {
+            bindings: vec![
+                Binding::new(Up, &["KeyW"], &["DPadUp", "North"], &["Up"]),
+                Binding::new(Down, &["KeyS"], &["DPadDown", "South"], &["Down"]),
+                Binding::new(Left, &["KeyA"], &["DPadLeft", "West"], &["Left"]),
+                Binding::new(Right, &["KeyD"], &["DPadRight", "East"], &["Right"]),
+                Binding::new(P2Up, &["ArrowUp"], &[], &[]),
+                Binding::new(P2Down, &["ArrowDown"], &[], &[]),
+                Binding::new(P2Left, &["ArrowLeft"], &[], &[]),
+                Binding::new(P2Right, &["ArrowRight"], &[], &[]),
+                Binding::new(MoveLeft, &["ArrowLeft"], &[], &["Left"]),
+                Binding::new(MoveRight, &["ArrowRight"], &[], &["Right"]),
+                Binding::new(MoveDown, &["ArrowDown"], &[], &["Down"]),
+                Binding::new(Slash, &["KeyZ"], &[], &["Z"]),
+                Binding::new(HeavySlash, &["KeyX"], &[], &["X"]),
+                Binding::new(Spin, &["KeyS"], &[], &["S"]),
+                Binding::new(Block, &["KeyC"], &[], &["C"]),
+                Binding::new(Dash, &["Space"], &[], &["Space"]),
+                Binding::new(SetClash, &["Enter"], &[], &["Enter"]),
+                Binding::new(SaveController, &["KeyP"], &[], &["P"]),
+                // Off the duel keys: D used to toggle edit mode mid-duel
+                Binding::new(ToggleEdit, &["Tab"], &[], &[]),
+                Binding::new(ToggleTouch, &["KeyT"], &[], &[]),
+            ],
+        }
+    }
+}
+
+impl InputBindings {
+    pub fn get(&self, action: InputAction) -> Option<&Binding> {
+        self.bindings.iter().find(|b| b.action == action)
+    }
+
+    /// These bindings with the defaults filling in every action they leave out. They are
+    /// validated first, so an action bound twice is reported rather than merged away.
+    pub fn over_defaults(self) -> Result<Self, String> {
+        self.validate()?;
+        let mut merged = Self::default();
+        for binding in &mut merged.bindings {
+            if let Some(own) = self.get(binding.action) {
+                *binding = own.clone();
+            }
+        }
+        Ok(merged)
+    }
+
+    /// Rejects an input bound to two actions that are read together.
+    pub fn validate(&self) -> Result<(), String> {
+        for (i, a) in self.bindings.iter().enumerate() {
+            for b in &self.bindings[i + 1..] {
+                if a.action == b.action {
+                    return Err(format!("{:?} is bound twice", a.action));
+                }
+                let (sa, sb) = (a.action.scope(), b.action.scope());
+                if sa != sb && sa != InputScope::Global && sb != InputScope::Global { continue; }
+                let shared = |x: &[String], y: &[String]| x.iter().find(|n| y.contains(n)).cloned();
+                if let Some(name) = shared(&a.keys, &b.keys).or(shared(&a.pad, &b.pad)).or(shared(&a.touch, &b.touch)) {
+                    return Err(format!("{} is bound to both {:?} and {:?}", name, a.action, b.action));
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+
+use crate::config::{
+    CALIBRATION_MAX_CORRECTION_MS, CALIBRATION_MAX_JITTER_MS, CALIBRATION_MIN_TRIALS, CALIBRATION_TRIALS, CALIBRATION_WARMUP_BEATS,
+    SWIPE_CALIBRATION_DISTANCE_SHARE, SWIPE_CALIBRATION_LOCK_SHARE, SWIPE_CALIBRATION_MIN_MM, SWIPE_CALIBRATION_SWIPES,
+    SWIPE_LOCK_MS_RANGE, SWIPE_MIN_MM_RANGE,
+};
+use crate::input::SwipeConfig;
+
+/// Input latency measured by tapping along to a metronome.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LatencyEstimate {
+    /// Median of tap minus beat; positive when taps land late.
+    pub offset_us: i64,
+    /// Standard deviation of the taps around their mean.
+    pub jitter_us: u64,
+    pub trials: u32,
+}
+
+impl LatencyEstimate {
+    /// Taken off measured reactions; early tappers get no bonus and the cut is capped.
+    pub fn correction_us(&self) -> u64 {
+        (self.offset_us.max(0) as u64).min(CALIBRATION_MAX_CORRECTION_MS * 1000)
+    }
+}
+
+/// Swipe thresholds fitted to the player's comfortable swipes.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SwipeEstimate {
+    pub min_swipe_mm: f32,
+    pub lock_ms: u64,
+    pub swipes: u32,
+}
+
+/// Everything calibrated for this device, saved to `DEVICE_FILE`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct DeviceProfile {
+    #[serde(default)]
+    pub latency: Option<LatencyEstimate>,
+    #[serde(default)]
+    pub swipe: Option<SwipeEstimate>,
+    /// Physical width of the screen in mm, entered with `--screen-mm`; measures the DPI.
+    #[serde(default)]
+    pub screen_mm: Option<f32>,
+}
+
+impl DeviceProfile {
+    /// Correction for every match, scored or not, like the GO presentation delay; capped and
+    /// recorded in the log, so a late calibration buys at most `CALIBRATION_MAX_CORRECTION_MS`.
+    pub fn input_offset_us(&self) -> u64 { self.latency.map_or(0, |l| l.correction_us()) }
+
+    /// Swipe thresholds at `dpi`, calibrated ones when there are any.
+    pub fn swipe_config(&self, dpi: f32) -> SwipeConfig {
+        match self.swipe {
+            Some(s) => SwipeConfig { dpi, min_swipe_mm: s.min_swipe_mm, lock_ms: s.lock_ms },
+            None => SwipeConfig::new(dpi),
+        }
+    }
+}
+
+/// A calibration run: beats as they reached the player, and each tap's offset to its beat.
+#[derive(Clone, Debug, Default)]
+pub struct LatencyCalibration {
+    pub beat_ms: u64,
+    /// When each beat's flash/sound reached the player.
+    pub beats_us: Vec<u64>,
+    /// Tap minus beat for every matched tap after the warm-up.
+    pub offsets_us: Vec<i64>,
+    // Index of the last beat a tap was matched to; each beat takes one tap
+    last_matched: Option<usize>,
+}
+
+impl LatencyCalibration {
+    pub fn new(beat_ms: u64) -> Self { Self { beat_ms, ..Self::default() } }
+
+    pub fn beat(&mut self, cue_us: u64) { self.beats_us.push(cue_us); }
+
+    /// Match a tap to the nearest beat within half a beat; returns its offset if it counted.
+    pub fn tap(&mut self, ts_us: u64) -> Option<i64> {
+        let half = self.beat_ms as i64 * 500;
+        let (i, offset) = self.beats_us.iter().enumerate()
+            .map(|(i, &b)| (i, ts_us as i64 - b as i64))
+            .min_by_key(|(_, d)| d.abs())?;
+        if offset.abs() > half || self.last_matched.is_some_and(|m| i <= m) { return None; }
+        self.last_matched = Some(i);
+        if i < CALIBRATION_WARMUP_BEATS { return None; }
+        self.offsets_us.push(offset);
+        Some(offset)
+    }
+
+    pub fn trials(&self) -> usize { self.offsets_us.len() }
+
+    /// Enough taps, or twice as many beats as needed went by (the player stopped tapping).
+    pub fn is_done(&self) -> bool {
+        self.trials() >= CALIBRATION_TRIALS || self.beats_us.len() >= CALIBRATION_WARMUP_BEATS + 2 * CALIBRATION_TRIALS
+    }
+
+    /// `None` with too few taps, or taps too scattered to trust.
+    pub fn estimate(&self) -> Option<LatencyEstimate> {
+        let n = self.trials();
+        if n < CALIBRATION_MIN_TRIALS { return None; }
+        let mut sorted = self.offsets_us.clone();
+        sorted.sort_unstable();
+        let median = if n.is_multiple_of(2) { (sorted[n / 2 - 1] + sorted[n / 2]) / 2 } else { sorted[n / 2] };
+        let mean = sorted.iter().sum::<i64>() as f64 / n as f64;
+        let var = sorted.iter().map(|&o| (o as f64 - mean).powi(2)).sum::<f64>() / n as f64;
+        let jitter_us = var.sqrt().round() as u64;
+        if jitter_us > CALIBRATION_MAX_JITTER_MS * 1000 { return None; }
+        Some(LatencyEstimate { offset_us: median, jitter_us, trials: n as u32 })
+    }
+}
+
+/// A swipe calibration run: length and duration of each comfortable swipe.
+#[derive(Clone, Debug, Default)]
+pub struct SwipeCalibration {
+    pub dpi: f32,
+    /// Finished swipes as (length mm, duration ms).
+    pub swipes: Vec<(f32, u64)>,
+    // Swipe in progress: accumulated motion and time since it started moving
+    current: Option<(f32, f32, u64)>,
+}
+
+impl SwipeCalibration {
+    pub fn new(dpi: f32) -> Self { Self { dpi, ..Self::default() } }
+
+    /// Motion of the pressed finger or mouse over the last `dt_ms`.
+    pub fn motion(&mut self, dt_ms: u64, dx: f32, dy: f32) {
+        match &mut self.current {
+            Some((x, y, ms)) => {
+                *x += dx;
+                *y += dy;
+                *ms += dt_ms;
+            }
+            // Time counts from the first frame that moved
+            None if dx != 0.0 || dy != 0.0 => self.current = Some((dx, dy, 0)),
+            None => {}
+        }
+    }
+
+    /// Finger or button released; returns the swipe if it was long enough to count.
+    pub fn release(&mut self) -> Option<(f32, u64)> {
+        let (x, y, ms) = self.current.take()?;
+        let mm = x.hypot(y) / self.dpi * 25.4;
+        if mm < SWIPE_CALIBRATION_MIN_MM { return None; }
+        self.swipes.push((mm, ms));
+        Some((mm, ms))
+    }
+
+    pub fn is_done(&self) -> bool { self.swipes.len() >= SWIPE_CALIBRATION_SWIPES }
+
+    /// Fire at a share of the median length, lock within a share of the median duration.
+    pub fn estimate(&self) -> Option<SwipeEstimate> {
+        if !self.is_done() { return None; }
+        let mut mm: Vec<f32> = self.swipes.iter().map(|s| s.0).collect();
+        let mut ms: Vec<u64> = self.swipes.iter().map(|s| s.1).collect();
+        mm.sort_by(f32::total_cmp);
+        ms.sort_unstable();
+        let (mid_mm, mid_ms) = (mm[mm.len() / 2], ms[ms.len() / 2]);
+        Some(SwipeEstimate {
+            min_swipe_mm: (mid_mm * SWIPE_CALIBRATION_DISTANCE_SHARE).clamp(SWIPE_MIN_MM_RANGE.0, SWIPE_MIN_MM_RANGE.1),
+            lock_ms: ((mid_ms as f32 * SWIPE_CALIBRATION_LOCK_SHARE).round() as u64).clamp(SWIPE_LOCK_MS_RANGE.0, SWIPE_LOCK_MS_RANGE.1),
+            swipes: self.swipes.len() as u32,
+        })
+    }
+}
@@ -0,0 +1,470 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::ai::AiTier;
+use crate::rng::XorShift32;
+use crate::state_machine::DuelConfig;
+use crate::logging::MatchLog;
+use crate::types::{ComboTiming, Handicap, MatchFormat, PosturePenalty, RevealMode, SurvivalSchedule};
+
+/// Calendar date (UTC, proleptic Gregorian) without pulling in a date crate.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl Date {
+    /// Date `days` after 1970-01-01 (Hinnant's civil-from-days).
+    pub fn from_days(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let year = (yoe + era * 400 + i64::from(month <= 2)) as i32;
+        Self { year, month, day }
+    }
+
+    /// Days since 1970-01-01.
+    pub fn days(&self) -> i64 {
+        let y = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = y.div_euclid(400);
+        let yoe = y.rem_euclid(400);
+        let m = i64::from(self.month);
+        let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + i64::from(self.day) - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+
+    pub fn today() -> Self {
+        let secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Self::from_days((secs / 86_400) as i64)
+    }
+
+    /// 0 = Monday .. 6 = Sunday.
+    pub fn weekday(&self) -> u8 {
+        (self.days() + 3).rem_euclid(7) as u8
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl std::str::FromStr for Date {
+    type Err = String;
+
+    // YYYY-MM-DD
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bad = || format!("bad date (want YYYY-MM-DD): {}", s);
+        let mut parts = s.trim().split('-');
+        let mut next = || parts.next().and_then(|p| p.parse::<i32>().ok()).ok_or_else(bad);
+        let (year, month, day) = (next()?, next()?, next()?);
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) { return Err(bad()); }
+        let date = Self { year, month: month as u8, day: day as u8 };
+        // Reject days past the end of the month (e.g. 02-30)
+        if Date::from_days(date.days()) != date { return Err(bad()); }
+        Ok(date)
+    }
+}
+
+/// The mutators a challenge is played under; everything else stays at `DuelConfig` defaults.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Ruleset {
+    pub clash: bool,
+    pub decoys: bool,
+    pub reveal: RevealMode,
+    pub combo_len: u8,
+    pub combo_timing: ComboTiming,
+    pub struggle: bool,
+    pub posture: bool,
+    pub posture_penalty: PosturePenalty,
+    pub draw_cut: bool,
+}
+
+impl Default for Ruleset {
+    fn default() -> Self { Self::from_config(&DuelConfig::default()) }
+}
+
+impl Ruleset {
+    pub fn from_config(cfg: &DuelConfig) -> Self {
+        Self {
+            clash: cfg.clash,
+            decoys: cfg.decoys,
+            reveal: cfg.reveal,
+            combo_len: cfg.combo_len,
+            combo_timing: cfg.combo_timing,
+            struggle: cfg.struggle,
+            posture: cfg.posture,
+            posture_penalty: cfg.posture_penalty,
+            draw_cut: cfg.draw_cut,
+        }
+    }
+
+    pub fn apply(&self, cfg: DuelConfig) -> DuelConfig {
+        DuelConfig {
+            clash: self.clash,
+            decoys: self.decoys,
+            reveal: self.reveal,
+            combo_len: self.combo_len,
+            combo_timing: self.combo_timing,
+            struggle: self.struggle,
+            posture: self.posture,
+            posture_penalty: self.posture_penalty,
+            draw_cut: self.draw_cut,
+            ..cfg
+        }
+    }
+}
+
+/// Window and reaction adjustments a challenge carries alongside its ruleset.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Timing {
+    pub human_handicap: Handicap,
+    pub ai_handicap: Handicap,
+    /// Only encoded for `MatchFormat::Survival`.
+    pub survival: SurvivalSchedule,
+}
+
+// FNV-1a, stable across platforms and releases (unlike std's hasher)
+pub(crate) fn fnv1a(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811C_9DC5u32, |h, b| (h ^ u32::from(*b)).wrapping_mul(0x0100_0193))
+}
+
+/// Everything needed to replay someone's duel: same seed, rules, format, timing and opponent.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Challenge {
+    pub seed: u32,
+    pub ruleset: Ruleset,
+    pub format: MatchFormat,
+    pub timing: Timing,
+    pub ai: AiTier,
+}
+
+const CODE_VERSION: u8 = 1;
+// Crockford base32: no I, L, O or U, so codes survive being read aloud or retyped
+const BASE32: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const CODE_GROUP: usize = 5;
+
+impl Challenge {
+    pub fn from_config(cfg: &DuelConfig, ai: AiTier) -> Self {
+        let timing = Timing { human_handicap: cfg.human_handicap, ai_handicap: cfg.ai_handicap, survival: cfg.survival };
+        Self { seed: cfg.seed, ruleset: Ruleset::from_config(cfg), format: cfg.format, timing, ai }
+    }
+
+    pub fn duel_config(&self) -> DuelConfig {
+        self.ruleset.apply(DuelConfig {
+            seed: self.seed,
+            format: self.format,
+            human_handicap: self.timing.human_handicap,
+            ai_handicap: self.timing.ai_handicap,
+            survival: self.timing.survival,
+            ..DuelConfig::default()
+        })
+    }
+
+    /// Base32 code with a 16-bit checksum, grouped in fives (e.g. `0G2AB-...`).
+    /// Millisecond values above 65535 saturate.
+    pub fn code(&self) -> String {
+        let mut bytes = self.to_bytes();
+        let sum = checksum(&bytes);
+        bytes.extend_from_slice(&sum.to_be_bytes());
+        let chars = base32_encode(&bytes);
+        let groups: Vec<String> = chars.chunks(CODE_GROUP).map(|c| c.iter().map(|&b| b as char).collect()).collect();
+        groups.join("-")
+    }
+
+    /// Parse a code; case, dashes and spaces are ignored, and O/I/L read as 0/1/1.
+    pub fn from_code(code: &str) -> Result<Self, String> {
+        let mut bytes = base32_decode(code)?;
+        if bytes.len() < 3 { return Err("challenge code too short".to_string()); }
+        let sum = u16::from_be_bytes([bytes[bytes.len() - 2], bytes[bytes.len() - 1]]);
+        bytes.truncate(bytes.len() - 2);
+        if checksum(&bytes) != sum { return Err("challenge code checksum mismatch (typo?)".to_string()); }
+        Self::from_bytes(&bytes)
+    }
+
+    // Layout (v1): version, seed, rule flags, reveal|combo_len, ai|format tag,
+    // format value, then optional handicaps and the survival schedule.
+    fn to_bytes(&self) -> Vec<u8> {
+        let r = &self.ruleset;
+        let handicapped = self.timing.human_handicap != Handicap::default() || self.timing.ai_handicap != Handicap::default();
+        let flags = u8::from(r.clash)
+            | u8::from(r.decoys) << 1
+            | u8::from(r.struggle) << 2
+            | u8::from(r.posture) << 3
+            | u8::from(r.draw_cut) << 4
+            | u8::from(r.combo_timing == ComboTiming::PerStep) << 5
+            | u8::from(r.posture_penalty == PosturePenalty::WidenOpponentTie) << 6
+            | u8::from(handicapped) << 7;
+        let reveal = match r.reveal { RevealMode::Always => 0, RevealMode::AtGo => 1, RevealMode::Memory => 2 };
+        let ai = match self.ai { AiTier::Dumb => 0, AiTier::Novice => 1, AiTier::Skilled => 2, AiTier::Master => 3 };
+        let (tag, value) = match self.format {
+            MatchFormat::FirstTo(n) => (0, u16::from(n)),
+            MatchFormat::Rounds(n) => (1, n),
+            MatchFormat::Survival => (2, 0),
+        };
+        let mut out = vec![CODE_VERSION];
+        out.extend_from_slice(&self.seed.to_be_bytes());
+        out.push(flags);
+        out.push(reveal | r.combo_len.min(63) << 2);
+        out.push(ai | tag << 2);
+        out.extend_from_slice(&value.to_be_bytes());
+        if handicapped {
+            for h in [self.timing.human_handicap, self.timing.ai_handicap] {
+                for ms in [h.extra_window_ms, h.reaction_offset_ms, h.extra_tie_ms] { push_ms(&mut out, ms); }
+                out.push(u8::from(h.forgive_wrong));
+            }
+        }
+        if self.format == MatchFormat::Survival {
+            let s = self.timing.survival;
+            for ms in [s.window_start_ms, s.window_step_ms, s.window_min_ms, s.tie_start_ms,
+                       s.tie_step_ms, s.tie_min_ms, s.ai_step_ms, s.ai_min_reaction_ms] {
+                push_ms(&mut out, ms);
+            }
+        }
+        out
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let mut rd = Reader { bytes, pos: 0 };
+        let version = rd.u8()?;
+        if version != CODE_VERSION { return Err(format!("unsupported challenge code version {}", version)); }
+        let seed = u32::from_be_bytes([rd.u8()?, rd.u8()?, rd.u8()?, rd.u8()?]);
+        let flags = rd.u8()?;
+        let bit = |n: u8| flags & (1 << n) != 0;
+        let packed = rd.u8()?;
+        let reveal = match packed & 0b11 {
+            0 => RevealMode::Always,
+            1 => RevealMode::AtGo,
+            2 => RevealMode::Memory,
+            n => return Err(format!("bad reveal mode {}", n)),
+        };
+        let combo_len = packed >> 2;
+        if combo_len == 0 { return Err("bad combo length 0".to_string()); }
+        let ruleset = Ruleset {
+            clash: bit(0),
+            decoys: bit(1),
+            reveal,
+            combo_len,
+            combo_timing: if bit(5) { ComboTiming::PerStep } else { ComboTiming::Total },
+            struggle: bit(2),
+            posture: bit(3),
+            posture_penalty: if bit(6) { PosturePenalty::WidenOpponentTie } else { PosturePenalty::ShrinkWindow },
+            draw_cut: bit(4),
+        };
+        let packed = rd.u8()?;
+        let ai = match packed & 0b11 { 0 => AiTier::Dumb, 1 => AiTier::Novice, 2 => AiTier::Skilled, _ => AiTier::Master };
+        let value = rd.u16()?;
+        let format = match packed >> 2 {
+            0 => MatchFormat::FirstTo(u8::try_from(value).map_err(|_| format!("bad first-to target {}", value))?),
+            1 => MatchFormat::Rounds(value),
+            2 => MatchFormat::Survival,
+            n => return Err(format!("bad match format {}", n)),
+        };
+        let mut timing = Timing::default();
+        if bit(7) {
+            for h in [&mut timing.human_handicap, &mut timing.ai_handicap] {
+                h.extra_window_ms = rd.ms()?;
+                h.reaction_offset_ms = rd.ms()?;
+                h.extra_tie_ms = rd.ms()?;
+                h.forgive_wrong = rd.u8()? != 0;
+            }
+        }
+        if format == MatchFormat::Survival {
+            let s = &mut timing.survival;
+            for ms in [&mut s.window_start_ms, &mut s.window_step_ms, &mut s.window_min_ms, &mut s.tie_start_ms,
+                       &mut s.tie_step_ms, &mut s.tie_min_ms, &mut s.ai_step_ms, &mut s.ai_min_reaction_ms] {
+                *ms = rd.ms()?;
+            }
+        }
+        if rd.pos != bytes.len() { return Err("challenge code has trailing data".to_string()); }
+        Ok(Self { seed, ruleset, format, timing, ai })
+    }
+}
+
+impl std::str::FromStr for Challenge {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Self::from_code(s) }
+}
+
+fn push_ms(out: &mut Vec<u8>, ms: u64) {
+    out.extend_from_slice(&u16::try_from(ms).unwrap_or(u16::MAX).to_be_bytes());
+}
+
+fn checksum(bytes: &[u8]) -> u16 {
+    let h = fnv1a(bytes);
+    (h >> 16) as u16 ^ h as u16
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn u8(&mut self) -> Result<u8, String> {
+        let b = *self.bytes.get(self.pos).ok_or("challenge code truncated")?;
+        self.pos += 1;
+        Ok(b)
+    }
+
+    fn u16(&mut self) -> Result<u16, String> { Ok(u16::from_be_bytes([self.u8()?, self.u8()?])) }
+
+    fn ms(&mut self) -> Result<u64, String> { self.u16().map(u64::from) }
+}
+
+fn base32_encode(bytes: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(bytes.len() * 8 / 5 + 1);
+    let (mut acc, mut bits) = (0u32, 0u32);
+    for &b in bytes {
+        acc = acc << 8 | u32::from(b);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32[(acc >> bits & 31) as usize]);
+        }
+    }
+    if bits > 0 { out.push(BASE32[(acc << (5 - bits) & 31) as usize]); }
+    out
+}
+
+fn base32_decode(code: &str) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    let (mut acc, mut bits) = (0u32, 0u32);
+    for c in code.chars().filter(|c| *c != '-' && !c.is_whitespace()) {
+        let c = match c.to_ascii_uppercase() { 'O' => '0', 'I' | 'L' => '1', c => c };
+        let v = BASE32.iter().position(|&b| b as char == c).ok_or_else(|| format!("bad character '{}' in challenge code", c))?;
+        acc = acc << 5 | v as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+        }
+    }
+    // The encoder pads the last character with zeros; anything else is a typo the checksum can't see
+    if acc & ((1 << bits) - 1) != 0 { return Err("challenge code has stray trailing bits (typo?)".to_string()); }
+    Ok(out)
+}
+
+/// One day's challenge: everything is derived from the date, so every player gets the same one.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DailyChallenge {
+    pub date: Date,
+    pub seed: u32,
+    pub ruleset: Ruleset,
+    pub ai: AiTier,
+}
+
+impl DailyChallenge {
+    pub fn for_date(date: Date) -> Self {
+        let seed = fnv1a(date.to_string().as_bytes());
+        // Separate stream so the ruleset doesn't shadow the duel's own openings
+        let mut rng = XorShift32::new(seed ^ 0x5EED_DA11);
+        let ruleset = Ruleset {
+            decoys: rng.next_u32().is_multiple_of(3),
+            reveal: match rng.next_u32() % 4 { 0 => RevealMode::AtGo, 1 => RevealMode::Memory, _ => RevealMode::Always },
+            combo_len: match rng.next_u32() % 5 { 0 => 3, 1 => 2, _ => 1 },
+            struggle: rng.next_u32().is_multiple_of(2),
+            posture: rng.next_u32().is_multiple_of(3),
+            draw_cut: rng.next_u32().is_multiple_of(4),
+            ..Ruleset::default()
+        };
+        // The week ramps up: Novice early on, Master at the weekend
+        let ai = match date.weekday() { 0 | 1 => AiTier::Novice, 2..=4 => AiTier::Skilled, _ => AiTier::Master };
+        Self { date, seed, ruleset, ai }
+    }
+
+    pub fn today() -> Self { Self::for_date(Date::today()) }
+
+    pub fn challenge(&self) -> Challenge {
+        let format = DuelConfig::default().format;
+        Challenge { seed: self.seed, ruleset: self.ruleset.clone(), format, timing: Timing::default(), ai: self.ai }
+    }
+
+    pub fn duel_config(&self) -> DuelConfig { self.challenge().duel_config() }
+
+    /// The day's challenge code; equal codes mean the same sequence was played.
+    pub fn code(&self) -> String { self.challenge().code() }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DailyResult {
+    pub date: Date,
+    pub code: String,
+    pub human_score: u8,
+    pub ai_score: u8,
+    /// Style score (see `scoring`).
+    pub score: u32,
+    /// Saved when the scored attempt starts; still set if it was never finished, which counts as a loss.
+    #[serde(default)]
+    pub forfeit: bool,
+}
+
+/// Locally saved daily results; only the first attempt of a day is kept.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DailyHistory {
+    pub results: Vec<DailyResult>,
+}
+
+impl DailyHistory {
+    pub fn played(&self, date: Date) -> bool {
+        self.results.iter().any(|r| r.date == date)
+    }
+
+    /// Store `result` unless that day was already played; returns whether it counted.
+    pub fn record(&mut self, result: DailyResult) -> bool {
+        if self.played(result.date) { return false; }
+        self.results.push(result);
+        true
+    }
+
+    /// Claim `date`'s scored attempt before it is played, as a forfeit until `settle` replaces it.
+    pub fn begin(&mut self, date: Date, code: String) -> bool {
+        self.record(DailyResult { date, code, human_score: 0, ai_score: 0, score: 0, forfeit: true })
+    }
+
+    /// Replace the day's forfeit marker with the finished result; returns whether it counted.
+    pub fn settle(&mut self, result: DailyResult) -> bool {
+        match self.results.iter_mut().find(|r| r.date == result.date && r.forfeit) {
+            Some(marker) => {
+                *marker = result;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// A finished challenge run, shared so a friend can compare against the same code.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChallengeExport {
+    pub code: String,
+    pub log: MatchLog,
+}
+
+impl ChallengeExport {
+    /// Read an export someone sent over; `None` if missing or malformed.
+    pub fn load(path: &str) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn score(&self) -> u32 { self.log.score.as_ref().map_or(0, |s| s.total) }
+
+    /// `Some(true)` if this run outscored `other`; `None` when they played different codes.
+    pub fn beats(&self, other: &ChallengeExport) -> Option<bool> {
+        let same = Challenge::from_code(&self.code).ok()? == Challenge::from_code(&other.code).ok()?;
+        same.then(|| self.score() > other.score())
+    }
+}
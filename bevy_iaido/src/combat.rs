@@ -1,4 +1,37 @@
-use crate::types::{Direction, Opening, Outcome};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{ComboTiming, Direction, Opening, Outcome, SwipeEvent};
+
+/// Per-side widening of the tie window. Extra tolerance for a side means the
+/// opponent must beat it by that much more before the speed check stops being a clash.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SpeedCheck {
+    pub human_extra_tie_ms: u64,
+    pub ai_extra_tie_ms: u64,
+    /// Subtracted from a side's time before comparing (handicap head start).
+    #[serde(default)]
+    pub human_offset_ms: u64,
+    #[serde(default)]
+    pub ai_offset_ms: u64,
+    /// Time from GO until the cue reached the side (display, audio and calibrated input latency).
+    #[serde(default)]
+    pub human_cue_us: u64,
+    #[serde(default)]
+    pub ai_cue_us: u64,
+}
+
+pub fn speed_outcome(human_ms: u64, ai_ms: u64, tie_window_ms: u64, check: SpeedCheck) -> Outcome {
+    speed_outcome_us(human_ms * 1000, ai_ms * 1000, tie_window_ms, check)
+}
+
+/// `speed_outcome` on microsecond times; the tie window stays in milliseconds.
+pub fn speed_outcome_us(human_us: u64, ai_us: u64, tie_window_ms: u64, check: SpeedCheck) -> Outcome {
+    let human_us = human_us.saturating_sub(check.human_offset_ms * 1000 + check.human_cue_us);
+    let ai_us = ai_us.saturating_sub(check.ai_offset_ms * 1000 + check.ai_cue_us);
+    if human_us + (tie_window_ms + check.ai_extra_tie_ms) * 1000 < ai_us { Outcome::HumanWin }
+    else if ai_us + (tie_window_ms + check.human_extra_tie_ms) * 1000 < human_us { Outcome::AiWin }
+    else { Outcome::Clash }
+}
 
 pub fn correct_direction_for(opening: Opening) -> Direction {
     match opening {
@@ -38,6 +71,22 @@ pub fn judge_outcome(
     human_react_ms: Option<u64>,
     ai_react_ms: Option<u64>,
     tie_window_ms: u64,
+) -> Outcome {
+    let us = |ms: u64| ms * 1000;
+    judge_outcome_with(human_opening, ai_opening, human_dir, ai_dir, human_react_ms.map(us), ai_react_ms.map(us), tie_window_ms, SpeedCheck::default())
+}
+
+/// Reactions in microseconds; the tie window and `check` in milliseconds.
+#[allow(clippy::too_many_arguments)]
+pub fn judge_outcome_with(
+    human_opening: Opening,
+    ai_opening: Opening,
+    human_dir: Option<Direction>,
+    ai_dir: Option<Direction>,
+    human_react_us: Option<u64>,
+    ai_react_us: Option<u64>,
+    tie_window_ms: u64,
+    check: SpeedCheck,
 ) -> Outcome {
     let correct_human = correct_direction_for(human_opening);
     let correct_ai = correct_direction_for(ai_opening);
@@ -73,10 +122,8 @@ pub fn judge_outcome(
             }
 
             // Neutral -> Speed Check
-            if let (Some(ht), Some(at)) = (human_react_ms, ai_react_ms) {
-                if ht + tie_window_ms < at { Outcome::HumanWin }
-                else if at + tie_window_ms < ht { Outcome::AiWin }
-                else { Outcome::Clash }
+            if let (Some(ht), Some(at)) = (human_react_us, ai_react_us) {
+                speed_outcome_us(ht, at, tie_window_ms, check)
             } else {
                 Outcome::Clash // Should not happen if dirs are Some
             }
@@ -85,4 +132,61 @@ pub fn judge_outcome(
         (None, Some(_)) => Outcome::AiWin,
         (None, None) => Outcome::Clash,
     }
-}
\ No newline at end of file
+}
+
+/// Every step must match its opening and only a side that finishes the combo can win; a round
+/// where neither finishes is a Clash however many steps each got through.
+#[allow(clippy::too_many_arguments)]
+pub fn judge_combo(
+    human_combo: &[Opening],
+    ai_combo: &[Opening],
+    human_steps: &[SwipeEvent],
+    ai_steps: &[SwipeEvent],
+    go_ts_ms: u64,
+    timing: ComboTiming,
+    tie_window_ms: u64,
+    check: SpeedCheck,
+) -> Outcome {
+    // Any wrong step is an instant loss, human checked first as in judge_outcome
+    let wrong = |combo: &[Opening], steps: &[SwipeEvent]| {
+        steps.iter().zip(combo).any(|(s, o)| s.dir != correct_direction_for(*o))
+    };
+    if wrong(human_combo, human_steps) { return Outcome::WrongHuman; }
+    if wrong(ai_combo, ai_steps) { return Outcome::WrongAi; }
+
+    let human_done = human_steps.len() == human_combo.len();
+    let ai_done = ai_steps.len() == ai_combo.len();
+    match (human_done, ai_done) {
+        (true, false) => return Outcome::HumanWin,
+        (false, true) => return Outcome::AiWin,
+        (false, false) => return Outcome::Clash,
+        (true, true) => {}
+    }
+
+    match timing {
+        ComboTiming::Total => {
+            let go_us = go_ts_ms * 1000;
+            let ht = human_steps.last().map_or(0, |e| e.ts_us() - go_us);
+            let at = ai_steps.last().map_or(0, |e| e.ts_us() - go_us);
+            speed_outcome_us(ht, at, tie_window_ms, check)
+        }
+        ComboTiming::PerStep => {
+            // Each step is timed from the previous one (GO for the first); most steps won takes it
+            let splits = |steps: &[SwipeEvent]| {
+                let mut prev = go_ts_ms * 1000;
+                steps.iter().map(move |e| { let d = e.ts_us() - prev; prev = e.ts_us(); d }).collect::<Vec<u64>>()
+            };
+            let mut balance = 0i32;
+            for (h, a) in splits(human_steps).into_iter().zip(splits(ai_steps)) {
+                match speed_outcome_us(h, a, tie_window_ms, check) {
+                    Outcome::HumanWin => balance += 1,
+                    Outcome::AiWin => balance -= 1,
+                    _ => {}
+                }
+            }
+            if balance > 0 { Outcome::HumanWin }
+            else if balance < 0 { Outcome::AiWin }
+            else { Outcome::Clash }
+        }
+    }
+}
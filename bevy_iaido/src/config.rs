@@ -12,14 +12,117 @@ pub const CLASH_INPUT_WINDOW_MS: u64 = 80;
 
 pub const DIRECTION_LOCK_MS: u64 = 20; // lock after ~20ms of motion
 pub const TIE_WINDOW_MS: u64 = 5; // ±5ms considered equal
+pub const AUDIO_LATENCY_MS: u32 = 0; // GO sound after the GO frame; set per device
+
+// Decoy mutator: at most one false cue per round, never too close to the real GO
+pub const DECOY_CHANCE_PERCENT: u32 = 60;
+pub const DECOY_MIN_AFTER_MS: u64 = 150; // earliest decoy after RandomDelay starts
+pub const DECOY_MIN_LEAD_MS: u64 = 200; // latest decoy before GO
+
+// Memory reveal: opening is shown this long after Standoff starts, then hidden until GO
+pub const MEMORY_FLASH_MS: u64 = 350;
+pub const MEMORY_STANDOFF_MS: u64 = 1000; // Standoff before each later round in memory mode
+
+// Combo rounds: each step after the first must land within this long of the previous one
+pub const COMBO_STEP_WINDOW_MS: u64 = 400;
+pub const MAX_COMBO_LEN: u8 = 4;
+
+// Sword-lock struggle after a clash: taps push a shared meter until time runs out or it pins
+pub const STRUGGLE_MS: u64 = 1500;
+pub const STRUGGLE_METER_MAX: i32 = 12;
+
+// Posture: drains per offence; hitting zero breaks posture for the next round
+pub const POSTURE_MAX: u8 = 100;
+pub const POSTURE_WRONG_FAST_MS: u64 = 250; // wrong inputs quicker than this are reckless
+pub const POSTURE_WRONG_DRAIN: u8 = 40;
+pub const POSTURE_CLASH_DRAIN: u8 = 25;
+pub const POSTURE_NEAR_TIE_MS: u64 = 20; // speed wins closer than this drain the loser
+pub const POSTURE_NEAR_TIE_DRAIN: u8 = 30;
+pub const POSTURE_BROKEN_WINDOW_CUT_MS: u64 = 200;
+pub const POSTURE_BROKEN_TIE_BONUS_MS: u64 = 15;
+
+// Draw-then-cut: draw window after GO, then a hidden delay before the cut cue
+pub const DRAW_WINDOW_MS: u64 = 500;
+pub const CUT_DELAY_MIN_MS: u64 = 250;
+pub const CUT_DELAY_MAX_MS: u64 = 700;
+
+// Style scoring: reaction buckets for S/A/B (slower wins are C), margin and streak bonuses
+pub const STYLE_S_MS: u32 = 180;
+pub const STYLE_A_MS: u32 = 240;
+pub const STYLE_B_MS: u32 = 320;
+pub const STYLE_MARGIN_BUMP_MS: u32 = 80; // winning by this much lifts the grade one rank
+pub const STYLE_MARGIN_CAP_MS: u32 = 150;
+pub const STYLE_STREAK_STEP_PCT: u32 = 25; // per consecutive win after the first
+pub const STYLE_STREAK_MAX_PCT: u32 = 200;
 
 // Match config
 pub const ROUNDS_TO_WIN: u8 = 2; // best of 3
+pub const TRAINING_ROUNDS: u16 = 20; // default drill session length
+
+// Survival defaults: per survived round the window and tie margin tighten, the AI speeds up
+pub const SURVIVAL_WINDOW_STEP_MS: u64 = 25;
+pub const SURVIVAL_MIN_WINDOW_MS: u64 = 250;
+pub const SURVIVAL_TIE_START_MS: u64 = 20;
+pub const SURVIVAL_TIE_STEP_MS: u64 = 1;
+pub const SURVIVAL_MIN_TIE_MS: u64 = 2;
+pub const SURVIVAL_AI_STEP_MS: u64 = 10;
+pub const SURVIVAL_AI_MIN_REACTION_MS: u64 = 140;
+
+// Player rating (Elo against the AI profiles' fixed ratings)
+pub const RATING_START: i32 = 1000;
+pub const RATING_K: i32 = 24;
+pub const RATING_K_PROVISIONAL: i32 = 40; // bigger swings while the rating settles
+pub const RATING_PROVISIONAL_GAMES: u32 = 10;
+pub const RATING_HISTORY_MAX: usize = 200; // oldest entries dropped beyond this
+
+// Latency calibration: tap along to a metronome; the median offset becomes the device's correction
+pub const CALIBRATION_BEAT_MS: u64 = 750;
+pub const CALIBRATION_TRIALS: usize = 20;
+pub const CALIBRATION_WARMUP_BEATS: usize = 3; // taps on the first beats are ignored while the player settles
+pub const CALIBRATION_MIN_TRIALS: usize = 12; // fewer matched taps give no estimate
+pub const CALIBRATION_MAX_CORRECTION_MS: u64 = 35; // never take more than this off a reaction (plausible device latency)
+pub const CALIBRATION_MAX_JITTER_MS: u64 = 30; // taps scattered wider than this give no estimate
 
 // Input thresholds
 // Minimum swipe distance in millimeters; scale by device DPI
 pub const MIN_SWIPE_MM: f32 = 7.0; // between 6–8 mm
 
+// Logical pixels per inch when the screen's physical size is unknown. Mobile logical pixels are
+// density-independent (Android dp); desktop scale factors say nothing about density, so it keeps
+// the old fixed default until `--screen-mm` gives the real size
+#[cfg(any(target_os = "android", target_os = "ios"))]
+pub const REFERENCE_DPI: f32 = 160.0;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+pub const REFERENCE_DPI: f32 = 320.0;
+
+// Swipe calibration: a few comfortable swipes set the minimum distance and direction lock
+pub const SWIPE_CALIBRATION_SWIPES: usize = 5;
+pub const SWIPE_CALIBRATION_MIN_MM: f32 = 3.0; // shorter strokes are taps, not swipes
+pub const SWIPE_CALIBRATION_DISTANCE_SHARE: f32 = 0.5; // fire halfway through a comfortable swipe
+pub const SWIPE_CALIBRATION_LOCK_SHARE: f32 = 0.2; // lock direction within its first fifth
+pub const SWIPE_MIN_MM_RANGE: (f32, f32) = (4.0, 12.0);
+pub const SWIPE_LOCK_MS_RANGE: (u64, u64) = (10, 40);
+
+// Swipe angle sectors: cardinals span ±SWIPE_CARDINAL_HALF_DEG around their axis, diagonals
+// the rest; swipes within SWIPE_DEAD_ZONE_DEG / 2 of a sector edge are rejected as ambiguous
+pub const SWIPE_CARDINAL_HALF_DEG: f32 = 22.5;
+pub const SWIPE_DEAD_ZONE_DEG: f32 = 6.0;
+
+// Two-finger gestures (UpDown / LeftRight): fingers land out of sync, so lock later than one finger
+pub const TWO_FINGER_LOCK_MS: u64 = 40;
+pub const TWO_FINGER_MIN_SCALE: f32 = 0.6; // each finger must travel this share of the min swipe
+pub const TWO_FINGER_OPPOSE_TOL_DEG: f32 = 40.0; // max deviation from exactly opposite motion
+pub const TWO_FINGER_AXIS_TOL_DEG: f32 = 30.0; // max deviation of the pair's axis from vertical/horizontal
+
+// Gamepad sticks: a flick leaves the dead zone and reaches GAMEPAD_FLICK_MAGNITUDE within
+// GAMEPAD_FLICK_MAX_MS; slower pushes are ignored until the stick returns to rest
+pub const GAMEPAD_STICK_DEADZONE: f32 = 0.25;
+pub const GAMEPAD_FLICK_MAGNITUDE: f32 = 0.85;
+pub const GAMEPAD_FLICK_MAX_MS: u64 = 90;
+
+// Keys pressed within this of the first one join its chord (two frames at 60 Hz)
+pub const KEY_CHORD_WINDOW_MS: u64 = 30;
+
 // Utility to convert mm to pixels given DPI (dots per inch)
 // 1 inch = 25.4 mm
 pub fn mm_to_px(mm: f32, dpi: f32) -> f32 {
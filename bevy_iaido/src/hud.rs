@@ -4,9 +4,15 @@ use bevy_tweening::*;
 use bevy_tweening::lens::*;
 use std::time::Duration;
 
+use crate::bindings::InputAction;
 use crate::combat::correct_direction_for;
-use crate::plugin::{DuelRuntime, GoCue, DebugState, AnimationEditMode};
-use crate::types::{DuelPhase, MatchState, Outcome, Actor};
+use crate::input_map::InputMap;
+use crate::config::{CALIBRATION_TRIALS, STRUGGLE_METER_MAX, SWIPE_CALIBRATION_SWIPES};
+use crate::scoring::{score_match, Grade};
+use crate::tournament::Slot;
+use crate::training::{is_hit, training_report};
+use crate::plugin::{DuelRuntime, GameMode, Records, TournamentRun, CalibrationRun, SwipeCalibrationRun, GoCue, CutCue, BeatCue, DecoyCue, DebugState, AnimationEditMode, IaidoSettings};
+use crate::types::{DecoyKind, DuelPhase, MatchState, Outcome, Actor};
 use crate::visuals::{AI_ATTACK_RANGE, AI_DODGE_DISTANCE, AI_STOP_DISTANCE, HIT_RANGE, MIN_SEPARATION, AiHealth, Character, CharacterControllerState, DeathRespawn, FrameIndex, FrameLibrary, ParryState, RespawnFadeIn};
 
 pub fn systems() -> impl Plugin {
@@ -22,7 +28,20 @@ impl Plugin for HudPlugin {
                 update_onboarding,
                 update_swipe_cues,
                 handle_go_event,
+                handle_decoy_event,
+                update_decoy_flash,
                 update_round_indicators,
+                update_struggle_meter,
+                update_posture_text,
+                update_grade_text,
+                update_training_text,
+                update_ghost_text,
+                update_survival_text,
+                update_daily_text,
+                update_challenge_text,
+                update_rating_text,
+                update_tournament_text,
+                update_calibration_text,
                 handle_restart_input,
                 update_debug_text,
             ));
@@ -46,6 +65,52 @@ struct SwipeCueText;
 #[derive(Component)]
 struct GoText;
 
+#[derive(Component)]
+struct StruggleText;
+
+#[derive(Component)]
+struct PostureText;
+
+#[derive(Component)]
+struct GradeText;
+
+#[derive(Component)]
+struct TrainingText;
+
+#[derive(Component)]
+struct RatingText;
+
+#[derive(Component)]
+struct GhostText;
+
+#[derive(Component)]
+struct SurvivalText;
+
+#[derive(Component)]
+struct DailyText;
+
+#[derive(Component)]
+struct ChallengeText;
+
+#[derive(Component)]
+struct TournamentText;
+
+/// Metronome prompt; brightens for `flash` seconds on every beat.
+#[derive(Component)]
+struct CalibrationText {
+    flash: f32,
+}
+
+const BEAT_FLASH_SECONDS: f32 = 0.1;
+
+#[derive(Component)]
+struct DecoyFlash {
+    remaining: f32,
+}
+
+const DECOY_FLASH_SECONDS: f32 = 0.12;
+const DECOY_FLASH_ALPHA: f32 = 0.35;
+
 fn setup_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
     let font_handle = {
         let font_path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
@@ -132,6 +197,298 @@ fn setup_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
         GoText,
     ));
 
+    // Sword-lock meter (Center, only during Struggle)
+    commands.spawn((
+        TextBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: font_handle.clone(),
+                    font_size: 48.0,
+                    color: Color::WHITE,
+                },
+            )
+            .with_justify(JustifyText::Center),
+            style: Style {
+                position_type: PositionType::Absolute,
+                align_self: AlignSelf::Center,
+                justify_self: JustifySelf::Center,
+                top: Val::Percent(30.0),
+                ..default()
+            },
+            z_index: ZIndex::Global(10),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        StruggleText,
+    ));
+
+    // Posture readout (Top Center, only with the posture system on)
+    commands.spawn((
+        TextBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: font_handle.clone(),
+                    font_size: 28.0,
+                    color: Color::WHITE,
+                },
+            )
+            .with_justify(JustifyText::Center),
+            style: Style {
+                position_type: PositionType::Absolute,
+                align_self: AlignSelf::Center,
+                justify_self: JustifySelf::Center,
+                top: Val::Px(20.0),
+                ..default()
+            },
+            z_index: ZIndex::Global(5),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        PostureText,
+    ));
+
+    // Round grade (below center, during ResultFlash after a human win)
+    commands.spawn((
+        TextBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: font_handle.clone(),
+                    font_size: 48.0,
+                    color: Color::WHITE,
+                },
+            )
+            .with_justify(JustifyText::Center),
+            style: Style {
+                position_type: PositionType::Absolute,
+                align_self: AlignSelf::Center,
+                justify_self: JustifySelf::Center,
+                top: Val::Percent(62.0),
+                ..default()
+            },
+            z_index: ZIndex::Global(10),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        GradeText,
+    ));
+
+    // Match verdict and rating change (below the grade, once a rated match ends)
+    commands.spawn((
+        TextBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: font_handle.clone(),
+                    font_size: 40.0,
+                    color: Color::WHITE,
+                },
+            )
+            .with_justify(JustifyText::Center),
+            style: Style {
+                position_type: PositionType::Absolute,
+                align_self: AlignSelf::Center,
+                justify_self: JustifySelf::Center,
+                top: Val::Percent(74.0),
+                ..default()
+            },
+            z_index: ZIndex::Global(10),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        RatingText,
+    ));
+
+    // Training feedback (Top Right): last rep and running accuracy, summary at the end
+    commands.spawn((
+        TextBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: font_handle.clone(),
+                    font_size: 24.0,
+                    color: Color::WHITE,
+                },
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                right: Val::Px(10.0),
+                ..default()
+            },
+            z_index: ZIndex::Global(5),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        TrainingText,
+    ));
+
+    // Ghost race readout (Top Center, under the posture line)
+    commands.spawn((
+        TextBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: font_handle.clone(),
+                    font_size: 28.0,
+                    color: Color::srgb(0.7, 0.85, 1.0),
+                },
+            )
+            .with_justify(JustifyText::Center),
+            style: Style {
+                position_type: PositionType::Absolute,
+                align_self: AlignSelf::Center,
+                justify_self: JustifySelf::Center,
+                top: Val::Px(56.0),
+                ..default()
+            },
+            z_index: ZIndex::Global(5),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        GhostText,
+    ));
+
+    // Survival streak (Top Left, under the debug line)
+    commands.spawn((
+        TextBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: font_handle.clone(),
+                    font_size: 32.0,
+                    color: Color::srgb(1.0, 0.85, 0.2),
+                },
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(40.0),
+                left: Val::Px(10.0),
+                ..default()
+            },
+            z_index: ZIndex::Global(5),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        SurvivalText,
+    ));
+
+    // Daily challenge banner (Top Left, same slot as survival; modes don't overlap)
+    commands.spawn((
+        TextBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: font_handle.clone(),
+                    font_size: 28.0,
+                    color: Color::srgb(0.6, 0.9, 1.0),
+                },
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(40.0),
+                left: Val::Px(10.0),
+                ..default()
+            },
+            z_index: ZIndex::Global(5),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        DailyText,
+    ));
+
+    // Challenge code and the rival's score (Top Left, same slot as daily)
+    commands.spawn((
+        TextBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: font_handle.clone(),
+                    font_size: 28.0,
+                    color: Color::srgb(0.6, 0.9, 1.0),
+                },
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(40.0),
+                left: Val::Px(10.0),
+                ..default()
+            },
+            z_index: ZIndex::Global(5),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        ChallengeText,
+    ));
+
+    // Current bracket match and who is on which side (Top Left, same slot as daily)
+    commands.spawn((
+        TextBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: font_handle.clone(),
+                    font_size: 28.0,
+                    color: Color::srgb(1.0, 0.85, 0.2),
+                },
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(40.0),
+                left: Val::Px(10.0),
+                ..default()
+            },
+            z_index: ZIndex::Global(5),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        TournamentText,
+    ));
+
+    // Latency calibration prompt and result (Center)
+    commands.spawn((
+        TextBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: font_handle.clone(),
+                    font_size: 40.0,
+                    color: Color::srgb(0.5, 0.5, 0.5),
+                },
+            )
+            .with_justify(JustifyText::Center),
+            style: Style {
+                position_type: PositionType::Absolute,
+                align_self: AlignSelf::Center,
+                justify_self: JustifySelf::Center,
+                top: Val::Percent(40.0),
+                ..default()
+            },
+            z_index: ZIndex::Global(10),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        CalibrationText { flash: 0.0 },
+    ));
+
+    // Decoy flash overlay (full screen, transparent until a decoy fires)
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            background_color: BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.0)),
+            z_index: ZIndex::Global(9),
+            ..default()
+        },
+        DecoyFlash { remaining: 0.0 },
+    ));
+
     // Swipe Cue Text (Center Top)
     commands.spawn((
         TextBundle {
@@ -191,7 +548,8 @@ fn update_onboarding(
     let mut vis = query.single_mut();
     if rt.machine.round_results.is_empty() {
         match rt.machine.phase {
-            DuelPhase::Standoff | DuelPhase::RandomDelay | DuelPhase::GoSignal | DuelPhase::InputWindow => {
+            DuelPhase::Standoff | DuelPhase::RandomDelay | DuelPhase::GoSignal | DuelPhase::DrawWindow
+            | DuelPhase::CutDelay | DuelPhase::CutSignal | DuelPhase::InputWindow => {
                 *vis = Visibility::Visible;
             }
             _ => {
@@ -207,6 +565,7 @@ fn update_swipe_cues(
     mut query: Query<(&mut Text, &mut Visibility), With<SwipeCueText>>,
     rt: Res<DuelRuntime>,
     debug_state: Res<DebugState>,
+    time: Res<Time>,
 ) {
     if matches!(*debug_state, DebugState::Animation) {
         if let Ok((_text, mut vis)) = query.get_single_mut() {
@@ -216,14 +575,31 @@ fn update_swipe_cues(
     }
     let show = matches!(
         rt.machine.phase,
-        DuelPhase::Standoff | DuelPhase::RandomDelay | DuelPhase::GoSignal | DuelPhase::InputWindow
+        DuelPhase::Standoff | DuelPhase::RandomDelay | DuelPhase::GoSignal | DuelPhase::DrawWindow
+            | DuelPhase::CutDelay | DuelPhase::CutSignal | DuelPhase::InputWindow
     );
 
     if let Ok((mut text, mut vis)) = query.get_single_mut() {
         if show {
             *vis = Visibility::Visible;
-            let dir = correct_direction_for(rt.machine.human_opening);
-            text.sections[0].value = format!("{}", dir);
+            let now_ms = (time.elapsed_seconds_f64() * 1000.0) as u64;
+            if rt.machine.phase == DuelPhase::DrawWindow && !rt.machine.has_drawn(Actor::Human) {
+                // Draw-then-cut: any swipe draws, the direction only matters for the cut
+                text.sections[0].value = "DRAW".to_string();
+            } else if rt.machine.opening_visible(now_ms) && !rt.machine.human_combo.is_empty() {
+                // Combo rounds list the steps still to enter
+                let done = rt.machine.steps(Actor::Human).len();
+                let remaining: Vec<String> = rt.machine.human_combo.iter()
+                    .skip(done)
+                    .map(|o| format!("{}", correct_direction_for(*o)))
+                    .collect();
+                text.sections[0].value = remaining.join(" > ");
+            } else if rt.machine.opening_visible(now_ms) {
+                let dir = correct_direction_for(rt.machine.human_opening);
+                text.sections[0].value = format!("{}", dir);
+            } else {
+                text.sections[0].value = "READY".to_string();
+            }
         } else {
             *vis = Visibility::Hidden;
         }
@@ -233,14 +609,18 @@ fn update_swipe_cues(
 fn handle_go_event(
     mut commands: Commands,
     mut go_rx: EventReader<GoCue>,
-    query: Query<Entity, With<GoText>>,
+    mut cut_rx: EventReader<CutCue>,
+    mut query: Query<(Entity, &mut Text), With<GoText>>,
     debug_state: Res<DebugState>,
 ) {
     if matches!(*debug_state, DebugState::Animation) {
         return;
     }
-    for _ in go_rx.read() {
-        if let Ok(entity) = query.get_single() {
+    // Same pop-in for both cues; only the label differs
+    let labels = go_rx.read().map(|_| "GO!").chain(cut_rx.read().map(|_| "CUT!")).collect::<Vec<_>>();
+    for label in labels {
+        if let Ok((entity, mut text)) = query.get_single_mut() {
+            text.sections[0].value = label.to_string();
             // Pop in
             commands.entity(entity)
                 .insert(Visibility::Visible)
@@ -271,6 +651,33 @@ fn handle_go_event(
     }
 }
 
+fn handle_decoy_event(
+    mut decoy_rx: EventReader<DecoyCue>,
+    mut query: Query<&mut DecoyFlash>,
+    debug_state: Res<DebugState>,
+) {
+    if matches!(*debug_state, DebugState::Animation) {
+        return;
+    }
+    for ev in decoy_rx.read() {
+        if ev.kind != DecoyKind::Flash { continue; }
+        if let Ok(mut flash) = query.get_single_mut() {
+            flash.remaining = DECOY_FLASH_SECONDS;
+        }
+    }
+}
+
+fn update_decoy_flash(
+    time: Res<Time>,
+    mut query: Query<(&mut DecoyFlash, &mut BackgroundColor)>,
+) {
+    for (mut flash, mut bg) in query.iter_mut() {
+        flash.remaining = (flash.remaining - time.delta_seconds()).max(0.0);
+        let alpha = DECOY_FLASH_ALPHA * flash.remaining / DECOY_FLASH_SECONDS;
+        bg.0 = Color::srgba(1.0, 1.0, 1.0, alpha);
+    }
+}
+
 fn update_round_indicators(
     mut query: Query<(&RoundIndicator, &mut Sprite, &mut Visibility)>,
     rt: Res<DuelRuntime>,
@@ -302,17 +709,279 @@ fn update_round_indicators(
     }
 }
 
+// Meter drawn as a rope: human side fills from the left, AI side from the right
+fn update_struggle_meter(
+    mut query: Query<(&mut Text, &mut Visibility), With<StruggleText>>,
+    rt: Res<DuelRuntime>,
+    debug_state: Res<DebugState>,
+) {
+    let Ok((mut text, mut vis)) = query.get_single_mut() else { return; };
+    if matches!(*debug_state, DebugState::Animation) || rt.machine.phase != DuelPhase::Struggle {
+        *vis = Visibility::Hidden;
+        return;
+    }
+    *vis = Visibility::Visible;
+    let max = STRUGGLE_METER_MAX;
+    let pos = (rt.machine.struggle_meter + max).clamp(0, 2 * max) as usize;
+    let mut bar = String::with_capacity(2 * max as usize + 1);
+    for i in 0..=(2 * max as usize) {
+        bar.push(if i == pos { '|' } else if i < pos { '=' } else { '-' });
+    }
+    text.sections[0].value = format!("PUSH!\n{}", bar);
+}
+
+fn update_posture_text(
+    mut query: Query<(&mut Text, &mut Visibility), With<PostureText>>,
+    rt: Res<DuelRuntime>,
+    debug_state: Res<DebugState>,
+) {
+    let Ok((mut text, mut vis)) = query.get_single_mut() else { return; };
+    if matches!(*debug_state, DebugState::Animation) || !rt.machine.cfg.posture {
+        *vis = Visibility::Hidden;
+        return;
+    }
+    *vis = Visibility::Visible;
+    let p = rt.machine.posture;
+    let side = |value: u8, broken: bool| if broken { "BROKEN".to_string() } else { format!("{}", value) };
+    text.sections[0].value = format!("POSTURE  YOU {} | AI {}", side(p.human, p.human_broken), side(p.ai, p.ai_broken));
+    text.sections[0].style.color = if p.human_broken { Color::srgb(1.0, 0.3, 0.3) } else { Color::WHITE };
+}
+
+fn update_grade_text(
+    mut query: Query<(&mut Text, &mut Visibility), With<GradeText>>,
+    rt: Res<DuelRuntime>,
+    debug_state: Res<DebugState>,
+) {
+    let Ok((mut text, mut vis)) = query.get_single_mut() else { return; };
+    let score = score_match(&rt.machine.round_results);
+    let last = score.rounds.last().copied().flatten();
+    let show = !matches!(*debug_state, DebugState::Animation) && rt.machine.phase == DuelPhase::ResultFlash;
+    let Some(round) = last.filter(|_| show) else {
+        *vis = Visibility::Hidden;
+        return;
+    };
+    *vis = Visibility::Visible;
+    let streak = if round.streak > 1 { format!("  x{} streak", round.streak) } else { String::new() };
+    text.sections[0].value = format!("{}  +{}{}\nSCORE {}", round.grade, round.points, streak, score.total);
+    text.sections[0].style.color = match round.grade {
+        Grade::S => Color::srgb(1.0, 0.85, 0.2),
+        Grade::A => Color::srgb(0.4, 1.0, 0.5),
+        Grade::B => Color::srgb(0.4, 0.7, 1.0),
+        Grade::C => Color::WHITE,
+    };
+}
+
+fn update_rating_text(
+    mut query: Query<(&mut Text, &mut Visibility), With<RatingText>>,
+    records: Res<Records>,
+    debug_state: Res<DebugState>,
+) {
+    let Ok((mut text, mut vis)) = query.get_single_mut() else { return; };
+    let show = !matches!(*debug_state, DebugState::Animation);
+    let Some(entry) = records.rating_change.as_ref().filter(|_| show) else {
+        *vis = Visibility::Hidden;
+        return;
+    };
+    *vis = Visibility::Visible;
+    let verdict = if entry.won { "VICTORY" } else { "DEFEAT" };
+    text.sections[0].value = format!("{}\nRATING {} ({:+})", verdict, entry.after, entry.delta());
+    text.sections[0].style.color = if entry.delta() >= 0 { Color::srgb(0.4, 1.0, 0.5) } else { Color::srgb(1.0, 0.45, 0.4) };
+}
+
+fn update_training_text(
+    mut query: Query<(&mut Text, &mut Visibility), With<TrainingText>>,
+    rt: Res<DuelRuntime>,
+    settings: Res<IaidoSettings>,
+    debug_state: Res<DebugState>,
+) {
+    let Ok((mut text, mut vis)) = query.get_single_mut() else { return; };
+    if matches!(*debug_state, DebugState::Animation) || settings.mode != GameMode::Training {
+        *vis = Visibility::Hidden;
+        return;
+    }
+    *vis = Visibility::Visible;
+    let report = training_report(&rt.machine.round_results);
+    let mut lines = Vec::new();
+    if rt.machine.match_state == MatchState::Finished {
+        lines.push(format!("DRILL DONE  {}/{} hits", report.hits(), report.attempts()));
+        lines.push("SLOWEST".to_string());
+        for d in report.slowest().into_iter().take(3) {
+            let avg = d.avg_ms().map_or("--".to_string(), |ms| format!("{} ms", ms));
+            lines.push(format!("{:?}  {}  {}%", d.opening, avg, d.accuracy()));
+        }
+        lines.push("Tap to go again".to_string());
+    } else {
+        if let Some(last) = rt.machine.round_results.last() {
+            let verdict = if is_hit(last) { "HIT" } else { "MISS" };
+            let ms = match (last.human_reaction_us, last.human_reaction_ms) {
+                (Some(us), _) => format!("{:.1} ms", us as f64 / 1000.0),
+                (None, Some(ms)) => format!("{} ms", ms),
+                (None, None) => "--".to_string(),
+            };
+            lines.push(format!("{:?}  {}  {}", last.human_opening, ms, verdict));
+        }
+        lines.push(format!("{}/{} hits", report.hits(), report.attempts()));
+    }
+    text.sections[0].value = lines.join("\n");
+}
+
+// Ghost's recorded reaction as the target, then both times once the round lands
+fn update_ghost_text(
+    mut query: Query<(&mut Text, &mut Visibility), With<GhostText>>,
+    rt: Res<DuelRuntime>,
+    debug_state: Res<DebugState>,
+) {
+    let Ok((mut text, mut vis)) = query.get_single_mut() else { return; };
+    let Some(ghost) = rt.ghost.as_ref().filter(|_| !matches!(*debug_state, DebugState::Animation)) else {
+        *vis = Visibility::Hidden;
+        return;
+    };
+    *vis = Visibility::Visible;
+    let ms = |v: Option<u64>| v.map_or("--".to_string(), |v| format!("{} ms", v));
+    let flashing = matches!(rt.machine.phase, DuelPhase::ResultFlash | DuelPhase::NextRound);
+    text.sections[0].value = match rt.machine.round_results.last().filter(|_| flashing) {
+        Some(last) => format!(
+            "YOU {}  |  GHOST {}",
+            ms(last.human_reaction_ms.map(u64::from)),
+            ms(last.ai_reaction_ms.map(u64::from)),
+        ),
+        None => format!("GHOST {}", ms(ghost.reaction_ms(rt.machine.round_results.len()))),
+    };
+}
+
+fn update_survival_text(
+    mut query: Query<(&mut Text, &mut Visibility), With<SurvivalText>>,
+    rt: Res<DuelRuntime>,
+    records: Res<Records>,
+    settings: Res<IaidoSettings>,
+    debug_state: Res<DebugState>,
+) {
+    let Ok((mut text, mut vis)) = query.get_single_mut() else { return; };
+    if matches!(*debug_state, DebugState::Animation) || settings.mode != GameMode::Survival {
+        *vis = Visibility::Hidden;
+        return;
+    }
+    *vis = Visibility::Visible;
+    let streak = rt.machine.survival_round();
+    let best = records.bests.survival_streak.max(streak);
+    text.sections[0].value = if rt.machine.match_state == MatchState::Finished {
+        let tag = if records.new_best { "  NEW BEST!" } else { "" };
+        format!("FALLEN AT {}{}\nBEST {}\nTap to try again", streak, tag, best)
+    } else {
+        format!("STREAK {}  |  BEST {}\nWindow {} ms", streak, best, rt.machine.input_window_for(Actor::Human))
+    };
+}
+
+fn update_daily_text(
+    mut query: Query<(&mut Text, &mut Visibility), With<DailyText>>,
+    records: Res<Records>,
+    settings: Res<IaidoSettings>,
+    debug_state: Res<DebugState>,
+) {
+    let Ok((mut text, mut vis)) = query.get_single_mut() else { return; };
+    let Some(challenge) = settings.daily.as_ref() else {
+        *vis = Visibility::Hidden;
+        return;
+    };
+    if matches!(*debug_state, DebugState::Animation) {
+        *vis = Visibility::Hidden;
+        return;
+    }
+    *vis = Visibility::Visible;
+    let status = if records.daily_scored {
+        "SCORED ATTEMPT".to_string()
+    } else {
+        match records.daily.results.iter().find(|r| r.date == challenge.date) {
+            Some(r) if r.forfeit => "PRACTICE  |  TODAY FORFEIT (LOSS)".to_string(),
+            Some(r) => format!("PRACTICE  |  TODAY {}-{}  {} pts", r.human_score, r.ai_score, r.score),
+            None => "PRACTICE".to_string(),
+        }
+    };
+    text.sections[0].value = format!("DAILY {}\n{}", challenge.code(), status);
+}
+
+fn update_challenge_text(
+    mut query: Query<(&mut Text, &mut Visibility), With<ChallengeText>>,
+    rt: Res<DuelRuntime>,
+    settings: Res<IaidoSettings>,
+    debug_state: Res<DebugState>,
+) {
+    let Ok((mut text, mut vis)) = query.get_single_mut() else { return; };
+    if matches!(*debug_state, DebugState::Animation) || settings.mode != GameMode::Challenge {
+        *vis = Visibility::Hidden;
+        return;
+    }
+    *vis = Visibility::Visible;
+    let to_beat = settings.rival.as_ref().map_or(String::new(), |r| format!("  |  TO BEAT {}", r.score()));
+    let finished = rt.machine.match_state != MatchState::InProgress;
+    text.sections[0].value = match (rt.export.as_ref().filter(|_| finished), settings.rival.as_ref()) {
+        (Some(mine), Some(rival)) => {
+            let verdict = match mine.beats(rival) {
+                Some(true) => "YOU WIN THE CHALLENGE",
+                Some(false) => "CHALLENGE LOST",
+                None => "DIFFERENT CHALLENGE",
+            };
+            format!("{} {} vs {}\nSaved to last_challenge.json", verdict, mine.score(), rival.score())
+        }
+        (Some(mine), None) => format!("SCORE {}\nCODE {}\nSaved to last_challenge.json", mine.score(), mine.code),
+        (None, _) => format!("CHALLENGE{}", to_beat),
+    };
+}
+
+fn update_tournament_text(
+    mut query: Query<(&mut Text, &mut Visibility), With<TournamentText>>,
+    run: Option<Res<TournamentRun>>,
+    rt: Res<DuelRuntime>,
+    debug_state: Res<DebugState>,
+) {
+    let Ok((mut text, mut vis)) = query.get_single_mut() else { return; };
+    let Some(run) = run.filter(|_| !matches!(*debug_state, DebugState::Animation)) else {
+        *vis = Visibility::Hidden;
+        return;
+    };
+    *vis = Visibility::Visible;
+    let bracket = &run.bracket;
+    let name = |i: usize| bracket.entrants[i].name.as_str();
+    if let Some(champ) = bracket.champion() {
+        text.sections[0].value = format!("CHAMPION: {}", name(champ));
+        return;
+    }
+    let Some(id) = run.current else {
+        text.sections[0].value = String::new();
+        return;
+    };
+    let m = &bracket.matches[id];
+    let header = format!("{:?} round {}", m.bracket, m.round);
+    text.sections[0].value = match (m.result, bracket.slot(m.a), bracket.slot(m.b)) {
+        (Some((sa, sb)), Slot::Entrant(a), Slot::Entrant(b)) => {
+            let (w, l, ws, ls) = if sa > sb { (a, b, sa, sb) } else { (b, a, sb, sa) };
+            format!("{}\n{} beats {} {}-{}\nTap for the next match", header, name(w), name(l), ws, ls)
+        }
+        (_, Slot::Entrant(a), Slot::Entrant(b)) => {
+            let (p1, p2) = if run.swapped { (b, a) } else { (a, b) };
+            if rt.hot_seat {
+                format!("{}\n{} (WASD / touch) vs {} (arrows)", header, name(p1), name(p2))
+            } else {
+                format!("{}\n{} vs {}", header, name(p1), name(p2))
+            }
+        }
+        _ => header,
+    };
+}
+
 fn handle_restart_input(
     mut rt: ResMut<DuelRuntime>,
     time: Res<Time>,
     mouse: Res<ButtonInput<MouseButton>>,
     touches: Res<Touches>,
     debug_state: Res<DebugState>,
+    settings: Res<IaidoSettings>,
 ) {
-    if matches!(*debug_state, DebugState::Animation) {
+    // Tournament taps advance the bracket instead (see run_tournament)
+    if matches!(*debug_state, DebugState::Animation) || settings.mode == GameMode::Tournament {
         return;
     }
-    if matches!(rt.machine.match_state, MatchState::HumanWon | MatchState::AiWon) {
+    if rt.machine.match_state != MatchState::InProgress {
         let tap = mouse.just_pressed(MouseButton::Left) || touches.any_just_pressed();
         if tap {
             let now_ms = (time.elapsed_seconds_f64() * 1000.0) as u64;
@@ -332,6 +1001,7 @@ fn update_debug_text(
     edit_mode: Res<AnimationEditMode>,
     ai_health: Res<AiHealth>,
     parry_state: Res<ParryState>,
+    map: Res<InputMap>,
 ) {
     if let Ok((mut text, mut vis)) = query.get_single_mut() {
         match *debug_state {
@@ -389,8 +1059,23 @@ fn update_debug_text(
                     AI_DODGE_DISTANCE,
                     MIN_SEPARATION,
                 );
+                // Help follows the input map, so rebound keys show as bound
+                let key = |a: InputAction| map.label(a);
+                let help = format!(
+                    "[{}/{}] Cycle Frame (Edit Mode)\n[{}] Set Slash + Play\n[{}] Set Clash + Play\n[{}] Up Attack: seq_1 press / seq_2 release\n[{}] Extended: seq_1 press / seq_2+seq_3 release\n[{}] Block: tap = frame1, hold = frame1+frame2\n[{}] Duel: press=duel, release=fast, double=spin\n[{}] Toggle Edit Mode\n[{}] Save controller",
+                    key(InputAction::MoveLeft),
+                    key(InputAction::MoveRight),
+                    key(InputAction::Dash),
+                    key(InputAction::SetClash),
+                    key(InputAction::Slash),
+                    key(InputAction::HeavySlash),
+                    key(InputAction::Block),
+                    key(InputAction::Spin),
+                    key(InputAction::ToggleEdit),
+                    key(InputAction::SaveController),
+                );
                 text.sections[0].value = format!(
-                    "ANIMATION PLAYGROUND\nFolder: {}\nHuman: {} ({})\nAI: {} ({})\nAI State: {} | HP: {} | Parry: {}\n{}\n{}\nEdit: {}\nSlash: {}\nClash: {}\n[Left/Right] Cycle Frame (Edit Mode)\n[Space] Set Slash + Play\n[Enter] Set Clash + Play\n[Z] Up Attack: seq_1 press / seq_2 release\n[X] Extended: seq_1 press / seq_2+seq_3 release\n[C] Block: tap = frame1, hold = frame1+frame2\n[S] Duel: press=duel, release=fast, double=spin\n[D] Toggle Edit Mode\n[P] Save controller",
+                    "ANIMATION PLAYGROUND\nFolder: {}\nHuman: {} ({})\nAI: {} ({})\nAI State: {} | HP: {} | Parry: {}\n{}\n{}\nEdit: {}\nSlash: {}\nClash: {}\n{}",
                     controller_state.controller_name,
                     human_idx,
                     human_name,
@@ -404,6 +1089,7 @@ fn update_debug_text(
                     if edit_mode.0 { "ON" } else { "OFF" },
                     controller_state.controller.slash_index,
                     controller_state.controller.clash_index,
+                    help,
                 );
                 return;
             }
@@ -427,6 +1113,17 @@ fn update_debug_text(
             "Waiting...".to_string()
         };
 
+        let swipe_line = match rt.touch.last.or(rt.swipe.last) {
+            Some(Ok(c)) => format!("{:?} (conf {:.2})", c.dir, c.confidence),
+            Some(Err(reason)) => format!("rejected: {}", reason),
+            None => "-".to_string(),
+        };
+        let two_finger_line = match rt.touch.two_finger.last {
+            Some(Ok(dir)) => format!("{:?}", dir),
+            Some(Err(reason)) => format!("rejected: {}", reason),
+            None => "-".to_string(),
+        };
+
         let state_str = format!("{:?}", m.match_state);
         let valid_dir = correct_direction_for(m.human_opening);
 
@@ -461,14 +1158,16 @@ fn update_debug_text(
             MIN_SEPARATION,
         );
         let info = format!(
-            "P1: {} | AI: {}\nRound: {}\nState: {}\nLast Outcome: {}\nP1 Swipe: {}\nInput Window: {}ms\nValid: {}\n{}\n{}",
+            "P1: {} | AI: {}\nRound: {}\nState: {}\nLast Outcome: {}\nP1 Swipe: {}\nClassifier: {}\nTwo-finger: {}\nInput Window: {}ms\nValid: {}\n{}\n{}",
             m.human_score,
             m.ai_score,
             m.round_results.len() + 1,
             state_str,
             last_outcome,
             p_swipe,
-            m.input_window_ms,
+            swipe_line,
+            two_finger_line,
+            m.input_window_for(Actor::Human),
             valid_dir,
             pos_line,
             range_line,
@@ -477,3 +1176,43 @@ fn update_debug_text(
         text.sections[0].value = info;
     }
 }
+
+// The calibration's only UI, so it shows in every debug state
+fn update_calibration_text(
+    mut query: Query<(&mut Text, &mut Visibility, &mut CalibrationText)>,
+    run: Option<Res<CalibrationRun>>,
+    swipe_run: Option<Res<SwipeCalibrationRun>>,
+    mut beat_rx: EventReader<BeatCue>,
+    time: Res<Time>,
+) {
+    let beat = beat_rx.read().count() > 0;
+    let Ok((mut text, mut vis, mut cal_text)) = query.get_single_mut() else { return; };
+    if let Some(run) = swipe_run {
+        *vis = Visibility::Visible;
+        text.sections[0].value = match (run.finished, run.result) {
+            (false, _) => format!("SWIPE AS YOU WOULD IN A DUEL\n{}/{}", run.cal.swipes.len(), SWIPE_CALIBRATION_SWIPES),
+            (true, Some(est)) => format!("MIN SWIPE {:.1} mm  LOCK {} ms\nSaved", est.min_swipe_mm, est.lock_ms),
+            (true, None) => "NO ESTIMATE".to_string(),
+        };
+        return;
+    }
+    let Some(run) = run else {
+        *vis = Visibility::Hidden;
+        return;
+    };
+    *vis = Visibility::Visible;
+    if beat { cal_text.flash = BEAT_FLASH_SECONDS; }
+    cal_text.flash = (cal_text.flash - time.delta_seconds()).max(0.0);
+    let lit = cal_text.flash > 0.0 && !run.finished;
+    text.sections[0].style.color = if lit { Color::WHITE } else { Color::srgb(0.5, 0.5, 0.5) };
+    text.sections[0].value = match (run.finished, run.result) {
+        (false, _) => format!("TAP ON THE BEAT\n{}/{}", run.cal.trials(), CALIBRATION_TRIALS),
+        (true, Some(est)) => format!(
+            "INPUT OFFSET {:+.1} ms  JITTER {:.1} ms\nSaved: reactions corrected by {:.1} ms",
+            est.offset_us as f64 / 1000.0,
+            est.jitter_us as f64 / 1000.0,
+            est.correction_us() as f64 / 1000.0,
+        ),
+        (true, None) => "TOO FEW TAPS ON THE BEAT\nNot saved; run --calibrate again".to_string(),
+    };
+}
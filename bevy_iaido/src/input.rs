@@ -1,5 +1,11 @@
-use crate::config::{mm_to_px, MIN_SWIPE_MM, DIRECTION_LOCK_MS};
-use crate::types::Direction;
+use std::fmt;
+
+use crate::config::{
+    mm_to_px, MIN_SWIPE_MM, DIRECTION_LOCK_MS, REFERENCE_DPI, SWIPE_CARDINAL_HALF_DEG, SWIPE_DEAD_ZONE_DEG,
+    TWO_FINGER_AXIS_TOL_DEG, TWO_FINGER_LOCK_MS, TWO_FINGER_MIN_SCALE, TWO_FINGER_OPPOSE_TOL_DEG,
+    GAMEPAD_FLICK_MAGNITUDE, GAMEPAD_FLICK_MAX_MS, GAMEPAD_STICK_DEADZONE,
+};
+use crate::types::{Actor, Direction};
 
 #[derive(Copy, Clone, Debug, Default)]
 pub struct SwipeSample {
@@ -11,15 +17,112 @@ pub struct SwipeSample {
 #[derive(Copy, Clone, Debug)]
 pub struct SwipeConfig {
     pub dpi: f32,
+    /// Swipe length that fires, and motion time before the direction locks (calibrated per player).
+    pub min_swipe_mm: f32,
+    pub lock_ms: u64,
 }
 
 impl SwipeConfig {
-    pub fn min_distance_px(&self) -> f32 { mm_to_px(MIN_SWIPE_MM, self.dpi) }
+    pub fn new(dpi: f32) -> Self { Self { dpi, min_swipe_mm: MIN_SWIPE_MM, lock_ms: DIRECTION_LOCK_MS } }
+
+    pub fn min_distance_px(&self) -> f32 { mm_to_px(self.min_swipe_mm, self.dpi) }
+}
+
+/// DPI of the logical pixels cursor and touch positions arrive in. With the window's physical
+/// width in pixels and millimetres it is measured; otherwise the platform's reference density.
+pub fn detect_dpi(scale_factor: f32, physical: Option<(f32, f32)>) -> f32 {
+    let scale = if scale_factor > 0.0 { scale_factor } else { 1.0 };
+    let physical_dpi = match physical {
+        Some((px, mm)) if px > 0.0 && mm > 0.0 => px / (mm / 25.4),
+        _ => REFERENCE_DPI * scale,
+    };
+    physical_dpi / scale
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum SwipeState { Idle, Moving, Locked }
 
+/// Why a swipe produced no direction.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SwipeReject {
+    NoMotion,
+    /// Too close to a cardinal/diagonal edge to call; angle is 0° = right, counter-clockwise.
+    DeadZone { angle_deg: f32 },
+    /// Two fingers moved the same way rather than apart or together.
+    NotOpposed,
+    /// Two opposed fingers, but on neither the vertical nor the horizontal axis.
+    OffAxis { angle_deg: f32 },
+    /// Stick pushed out too slowly to count as a flick.
+    TooSlow { ms: u64 },
+}
+
+impl fmt::Display for SwipeReject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SwipeReject::NoMotion => write!(f, "no motion"),
+            SwipeReject::DeadZone { angle_deg } => write!(f, "dead zone at {:.1}°", angle_deg),
+            SwipeReject::NotOpposed => write!(f, "fingers not opposed"),
+            SwipeReject::OffAxis { angle_deg } => write!(f, "two-finger axis off at {:.1}°", angle_deg),
+            SwipeReject::TooSlow { ms } => write!(f, "stick flick too slow ({} ms)", ms),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Classified {
+    pub dir: Direction,
+    /// 1.0 on the sector's axis, falling to 0.0 at the dead-zone edge.
+    pub confidence: f32,
+}
+
+/// Maps a swipe vector to one of the 8 directions by angle sector.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SwipeClassifier {
+    /// Cardinal sectors span ±this around their axis; diagonals get the remaining 45° - this.
+    pub cardinal_half_deg: f32,
+    /// Full width of the rejected band centred on every sector edge.
+    pub dead_zone_deg: f32,
+}
+
+impl Default for SwipeClassifier {
+    fn default() -> Self { Self { cardinal_half_deg: SWIPE_CARDINAL_HALF_DEG, dead_zone_deg: SWIPE_DEAD_ZONE_DEG } }
+}
+
+impl SwipeClassifier {
+    /// `dy > 0` is up, matching `primary_direction`.
+    pub fn classify(&self, dx: f32, dy: f32) -> Result<Classified, SwipeReject> {
+        if dx == 0.0 && dy == 0.0 { return Err(SwipeReject::NoMotion); }
+        let angle_deg = dy.atan2(dx).to_degrees().rem_euclid(360.0);
+        // Offset from the nearest cardinal axis, 0..=45
+        let octant = angle_deg % 90.0;
+        let off = octant.min(90.0 - octant);
+        let half_dz = self.dead_zone_deg / 2.0;
+        if (off - self.cardinal_half_deg).abs() < half_dz {
+            return Err(SwipeReject::DeadZone { angle_deg });
+        }
+        let (dir, width, from_axis) = if off < self.cardinal_half_deg {
+            let dir = match ((angle_deg + 45.0) / 90.0) as u32 % 4 {
+                0 => Direction::Right,
+                1 => Direction::Up,
+                2 => Direction::Left,
+                _ => Direction::Down,
+            };
+            (dir, self.cardinal_half_deg, off)
+        } else {
+            let dir = match (angle_deg / 90.0) as u32 % 4 {
+                0 => Direction::UpRight,
+                1 => Direction::UpLeft,
+                2 => Direction::DownLeft,
+                _ => Direction::DownRight,
+            };
+            (dir, 45.0 - self.cardinal_half_deg, 45.0 - off)
+        };
+        let usable = (width - half_dz).max(f32::EPSILON);
+        let confidence = ((usable - from_axis) / usable).clamp(0.0, 1.0);
+        Ok(Classified { dir, confidence })
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct SwipeDetector {
     pub state: SwipeState,
@@ -28,11 +131,30 @@ pub struct SwipeDetector {
     pub accum_dx: f32,
     pub accum_dy: f32,
     pub committed: bool,
+    pub classifier: SwipeClassifier,
+    /// Verdict on the most recent swipe; survives `reset` so debug views can show it.
+    pub last: Option<Result<Classified, SwipeReject>>,
+    /// Timestamp of the previous sample, the start of the interval the next one covers.
+    pub prev_ts_us: Option<u64>,
 }
 
 impl SwipeDetector {
     pub fn new() -> Self {
-        Self { state: SwipeState::Idle, lock_dir: None, elapsed_ms: 0, accum_dx: 0.0, accum_dy: 0.0, committed: false }
+        Self::with_classifier(SwipeClassifier::default())
+    }
+
+    pub fn with_classifier(classifier: SwipeClassifier) -> Self {
+        Self {
+            state: SwipeState::Idle,
+            lock_dir: None,
+            elapsed_ms: 0,
+            accum_dx: 0.0,
+            accum_dy: 0.0,
+            committed: false,
+            classifier,
+            last: None,
+            prev_ts_us: None,
+        }
     }
 
     pub fn reset(&mut self) {
@@ -42,9 +164,18 @@ impl SwipeDetector {
         self.accum_dx = 0.0;
         self.accum_dy = 0.0;
         self.committed = false;
+        self.prev_ts_us = None;
     }
 
     pub fn update(&mut self, cfg: &SwipeConfig, sample: SwipeSample) -> Option<Direction> {
+        let ts_us = self.prev_ts_us.unwrap_or(0) + sample.dt_ms * 1000;
+        self.update_at(cfg, sample, ts_us).map(|(dir, _)| dir)
+    }
+
+    /// `update` for a sample taken at `ts_us`. A swipe comes back with the moment it crossed
+    /// the distance threshold, interpolated between the previous sample and this one.
+    pub fn update_at(&mut self, cfg: &SwipeConfig, sample: SwipeSample, ts_us: u64) -> Option<(Direction, u64)> {
+        let prev_us = self.prev_ts_us.replace(ts_us).unwrap_or(ts_us).min(ts_us);
         match self.state {
             SwipeState::Idle => {
                 if sample.dx != 0.0 || sample.dy != 0.0 {
@@ -52,20 +183,37 @@ impl SwipeDetector {
                 }
             }
             SwipeState::Moving => {
+                let before = self.accum_dx.hypot(self.accum_dy);
                 self.elapsed_ms += sample.dt_ms;
                 self.accum_dx += sample.dx;
                 self.accum_dy += sample.dy;
 
-                if !self.committed && self.elapsed_ms >= DIRECTION_LOCK_MS {
-                    self.lock_dir = Some(primary_direction(self.accum_dx, self.accum_dy));
+                if !self.committed && self.elapsed_ms >= cfg.lock_ms {
+                    let verdict = self.classifier.classify(self.accum_dx, self.accum_dy);
+                    self.last = Some(verdict);
                     self.committed = true;
+                    match verdict {
+                        Ok(c) => self.lock_dir = Some(c.dir),
+                        // Ambiguous: swallow the rest of this gesture
+                        Err(_) => {
+                            self.state = SwipeState::Locked;
+                            return None;
+                        }
+                    }
                 }
                 if self.committed {
-                    let dist2 = self.accum_dx * self.accum_dx + self.accum_dy * self.accum_dy;
+                    let dist = self.accum_dx.hypot(self.accum_dy);
                     let min = cfg.min_distance_px();
-                    if dist2 >= min * min {
+                    if dist >= min {
                         self.state = SwipeState::Locked;
-                        return self.lock_dir;
+                        // Crossed before the direction locked: the swipe counts from the lock
+                        let at_us = if before < min && dist > before {
+                            let f = ((min - before) / (dist - before)) as f64;
+                            prev_us + ((ts_us - prev_us) as f64 * f).round() as u64
+                        } else {
+                            ts_us
+                        };
+                        return self.lock_dir.map(|dir| (dir, at_us));
                     }
                 }
             }
@@ -75,24 +223,314 @@ impl SwipeDetector {
     }
 }
 
-pub fn primary_direction(dx: f32, dy: f32) -> Direction {
-    let adx = dx.abs();
-    let ady = dy.abs();
-    let max = adx.max(ady);
-    if max == 0.0 { return Direction::Up; } // Fallback
-
-    // Check for diagonal: if the smaller component is at least 40% of the larger
-    if adx > 0.4 * max && ady > 0.4 * max {
-        if dx > 0.0 {
-            return if dy > 0.0 { Direction::UpRight } else { Direction::DownRight };
-        } else {
-            return if dy > 0.0 { Direction::UpLeft } else { Direction::DownLeft };
+/// Two-finger gestures for the opposed-pair directions: a vertical pinch/spread or opposite
+/// up/down swipes give `UpDown`, the horizontal equivalents `LeftRight`.
+#[derive(Copy, Clone, Debug)]
+pub struct TwoFingerDetector {
+    pub state: SwipeState,
+    pub elapsed_ms: u64,
+    /// Accumulated motion of the first and second finger.
+    pub a: (f32, f32),
+    pub b: (f32, f32),
+    /// Verdict on the most recent gesture; survives `reset`.
+    pub last: Option<Result<Direction, SwipeReject>>,
+}
+
+impl Default for TwoFingerDetector {
+    fn default() -> Self {
+        Self { state: SwipeState::Idle, elapsed_ms: 0, a: (0.0, 0.0), b: (0.0, 0.0), last: None }
+    }
+}
+
+impl TwoFingerDetector {
+    pub fn reset(&mut self) {
+        *self = Self { last: self.last, ..Self::default() };
+    }
+
+    /// Feed this frame's motion of both fingers (zero for a finger that didn't move).
+    pub fn update(&mut self, cfg: &SwipeConfig, dt_ms: u64, da: (f32, f32), db: (f32, f32)) -> Option<Direction> {
+        match self.state {
+            SwipeState::Locked => return None,
+            SwipeState::Idle if da == (0.0, 0.0) && db == (0.0, 0.0) => return None,
+            _ => self.state = SwipeState::Moving,
         }
+        self.elapsed_ms += dt_ms;
+        self.a = (self.a.0 + da.0, self.a.1 + da.1);
+        self.b = (self.b.0 + db.0, self.b.1 + db.1);
+        // Both fingers need to have really moved before the pair is judged
+        let min = cfg.min_distance_px() * TWO_FINGER_MIN_SCALE;
+        let far = |(x, y): (f32, f32)| x * x + y * y >= min * min;
+        if self.elapsed_ms < TWO_FINGER_LOCK_MS || !far(self.a) || !far(self.b) { return None; }
+        let verdict = classify_pair(self.a, self.b);
+        self.last = Some(verdict);
+        self.state = SwipeState::Locked;
+        verdict.ok()
     }
+}
 
-    if adx > ady {
-        if dx > 0.0 { Direction::Right } else { Direction::Left }
+/// Opposed-pair direction for two finger vectors (`dy > 0` is up).
+pub fn classify_pair(a: (f32, f32), b: (f32, f32)) -> Result<Direction, SwipeReject> {
+    let (la, lb) = (a.0.hypot(a.1), b.0.hypot(b.1));
+    if la == 0.0 || lb == 0.0 { return Err(SwipeReject::NoMotion); }
+    let cos = (a.0 * b.0 + a.1 * b.1) / (la * lb);
+    if cos > -TWO_FINGER_OPPOSE_TOL_DEG.to_radians().cos() { return Err(SwipeReject::NotOpposed); }
+    // Axis the fingers separate along, 0..180 (0 = horizontal)
+    let angle_deg = (a.1 - b.1).atan2(a.0 - b.0).to_degrees().rem_euclid(180.0);
+    if (angle_deg - 90.0).abs() <= TWO_FINGER_AXIS_TOL_DEG {
+        Ok(Direction::UpDown)
+    } else if angle_deg.min(180.0 - angle_deg) <= TWO_FINGER_AXIS_TOL_DEG {
+        Ok(Direction::LeftRight)
     } else {
-        if dy > 0.0 { Direction::Up } else { Direction::Down }
+        Err(SwipeReject::OffAxis { angle_deg })
     }
 }
+
+/// Which finger's swipe counts when several are down.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum SwipeOwner {
+    /// The earliest finger still down.
+    #[default]
+    FirstDown,
+    /// Whichever finger has travelled furthest.
+    LargestDisplacement,
+}
+
+/// Touch gestures keyed by touch id: one `SwipeDetector` per finger plus the two-finger detector.
+/// At most one direction fires per gesture (until every finger lifts).
+#[derive(Clone, Debug)]
+pub struct TouchGestures {
+    pub owner: SwipeOwner,
+    classifier: SwipeClassifier,
+    /// Fingers down, first touch first.
+    fingers: Vec<(u64, SwipeDetector)>,
+    pub two_finger: TwoFingerDetector,
+    fired: bool,
+    /// Owner's latest single-finger verdict; kept after the fingers lift.
+    pub last: Option<Result<Classified, SwipeReject>>,
+    /// Timestamp of the latest frame fed in.
+    clock_us: u64,
+}
+
+impl TouchGestures {
+    pub fn new(owner: SwipeOwner, classifier: SwipeClassifier) -> Self {
+        Self { owner, classifier, fingers: Vec::new(), two_finger: TwoFingerDetector::default(), fired: false, last: None, clock_us: 0 }
+    }
+
+    pub fn down(&mut self, id: u64) {
+        if self.fingers.iter().all(|(f, _)| *f != id) {
+            self.fingers.push((id, SwipeDetector::with_classifier(self.classifier)));
+        }
+    }
+
+    pub fn up(&mut self, id: u64) {
+        self.fingers.retain(|(f, _)| *f != id);
+        if self.fingers.len() < 2 { self.two_finger.reset(); }
+        if self.fingers.is_empty() { self.fired = false; }
+    }
+
+    /// Drop every finger, e.g. when the sword-lock takes over the screen.
+    pub fn clear(&mut self) {
+        self.fingers.clear();
+        self.two_finger.reset();
+        self.fired = false;
+    }
+
+    pub fn finger_count(&self) -> usize { self.fingers.len() }
+
+    pub fn detector(&self, id: u64) -> Option<&SwipeDetector> {
+        self.fingers.iter().find(|(f, _)| *f == id).map(|(_, d)| d)
+    }
+
+    fn owner_index(&self) -> Option<usize> {
+        match self.owner {
+            SwipeOwner::FirstDown => (!self.fingers.is_empty()).then_some(0),
+            SwipeOwner::LargestDisplacement => {
+                let dist2 = |d: &SwipeDetector| d.accum_dx * d.accum_dx + d.accum_dy * d.accum_dy;
+                // Ties go to the earlier finger
+                (0..self.fingers.len()).rev().max_by(|&x, &y| dist2(&self.fingers[x].1).total_cmp(&dist2(&self.fingers[y].1)))
+            }
+        }
+    }
+
+    /// Feed one frame of motion; `delta(id)` is each finger's movement since the last frame.
+    pub fn update(&mut self, cfg: &SwipeConfig, dt_ms: u64, delta: impl Fn(u64) -> (f32, f32)) -> Option<Direction> {
+        let ts_us = self.clock_us + dt_ms * 1000;
+        self.update_at(cfg, dt_ms, ts_us, delta).map(|(dir, _)| dir)
+    }
+
+    /// `update` for a frame sampled at `ts_us`; a fired direction comes with its swipe's
+    /// interpolated threshold crossing, or `ts_us` for a two-finger gesture.
+    pub fn update_at(&mut self, cfg: &SwipeConfig, dt_ms: u64, ts_us: u64, delta: impl Fn(u64) -> (f32, f32)) -> Option<(Direction, u64)> {
+        self.clock_us = ts_us;
+        let mut hits = Vec::new();
+        for (i, (id, det)) in self.fingers.iter_mut().enumerate() {
+            let (dx, dy) = delta(*id);
+            if let Some(hit) = det.update_at(cfg, SwipeSample { dt_ms, dx, dy }, ts_us) { hits.push((i, hit)); }
+        }
+        let pair = match self.fingers.as_slice() {
+            [(a, _), (b, _), ..] => self.two_finger.update(cfg, dt_ms, delta(*a), delta(*b)),
+            _ => None,
+        };
+        let owner = self.owner_index();
+        if let Some(verdict) = owner.and_then(|i| self.fingers[i].1.last) { self.last = Some(verdict); }
+        if self.fired { return None; }
+        if let Some(dir) = pair {
+            self.fired = true;
+            return Some((dir, ts_us));
+        }
+        let owner = owner?;
+        let (_, hit) = hits.into_iter().find(|(i, _)| *i == owner)?;
+        // Another finger on the move means a two-finger gesture: leave it to the pair detector
+        let rest = cfg.min_distance_px() * TWO_FINGER_MIN_SCALE;
+        let others_still = self.fingers.iter().enumerate()
+            .filter(|(i, _)| *i != owner)
+            .all(|(_, (_, d))| d.accum_dx * d.accum_dx + d.accum_dy * d.accum_dy < rest * rest);
+        if !others_still { return None; }
+        self.fired = true;
+        Some(hit)
+    }
+}
+
+/// Direction from held up/down/left/right buttons: opposed pairs first, then diagonals.
+pub fn held_direction(up: bool, down: bool, left: bool, right: bool) -> Option<Direction> {
+    if up && down { Some(Direction::UpDown) }
+    else if left && right { Some(Direction::LeftRight) }
+    else if up && right { Some(Direction::UpRight) }
+    else if up && left { Some(Direction::UpLeft) }
+    else if down && right { Some(Direction::DownRight) }
+    else if down && left { Some(Direction::DownLeft) }
+    else if up { Some(Direction::Up) }
+    else if down { Some(Direction::Down) }
+    else if left { Some(Direction::Left) }
+    else if right { Some(Direction::Right) }
+    else { None }
+}
+
+/// Collects up/down/left/right presses for `window_ms` before committing a direction, so two
+/// keys landing on different frames still make a diagonal or opposed pair.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct KeyChord {
+    pub window_ms: u64,
+    /// Keys seen since the chord opened: up, down, left, right.
+    pub seen: [bool; 4],
+    /// First press of the open chord.
+    pub start_us: Option<u64>,
+}
+
+impl KeyChord {
+    pub fn new(window_ms: u64) -> Self { Self { window_ms, ..Self::default() } }
+
+    /// Feed the held keys at `ts_us`, `fresh` when one of them was just pressed. The committed
+    /// direction is stamped at the chord's first press, so waiting costs no reaction time.
+    pub fn update(&mut self, ts_us: u64, fresh: bool, held: [bool; 4]) -> Option<(Direction, u64)> {
+        let start_us = match self.start_us {
+            Some(t) => t,
+            None if fresh => *self.start_us.insert(ts_us),
+            None => return None,
+        };
+        for (seen, h) in self.seen.iter_mut().zip(held) { *seen |= h; }
+        // A second key completes the chord; a lone key waits out the window
+        let keys = self.seen.iter().filter(|s| **s).count();
+        if keys < 2 && ts_us < start_us + self.window_ms * 1000 { return None; }
+        let [up, down, left, right] = std::mem::take(&mut self.seen);
+        self.start_us = None;
+        held_direction(up, down, left, right).map(|dir| (dir, start_us))
+    }
+
+    pub fn reset(&mut self) { *self = Self::new(self.window_ms); }
+}
+
+/// Thresholds for analog stick flicks (stick axes in -1..=1).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FlickConfig {
+    /// Below this magnitude the stick is at rest.
+    pub deadzone: f32,
+    /// Magnitude the stick must reach to fire.
+    pub magnitude: f32,
+    /// Longest time from leaving the dead zone to reaching `magnitude`.
+    pub max_ms: u64,
+}
+
+impl Default for FlickConfig {
+    fn default() -> Self {
+        Self { deadzone: GAMEPAD_STICK_DEADZONE, magnitude: GAMEPAD_FLICK_MAGNITUDE, max_ms: GAMEPAD_FLICK_MAX_MS }
+    }
+}
+
+/// Analog stick flicks, classified through the same sectors as swipes (`y > 0` is up).
+#[derive(Copy, Clone, Debug)]
+pub struct StickFlick {
+    pub cfg: FlickConfig,
+    pub classifier: SwipeClassifier,
+    /// Idle at rest, Moving while heading out, Locked once fired or too slow until back at rest.
+    pub state: SwipeState,
+    pub elapsed_ms: u64,
+    /// Verdict on the most recent flick.
+    pub last: Option<Result<Classified, SwipeReject>>,
+}
+
+impl StickFlick {
+    pub fn new(cfg: FlickConfig, classifier: SwipeClassifier) -> Self {
+        Self { cfg, classifier, state: SwipeState::Idle, elapsed_ms: 0, last: None }
+    }
+
+    /// Feed the stick position after `dt_ms`.
+    pub fn update(&mut self, dt_ms: u64, x: f32, y: f32) -> Option<Direction> {
+        let mag = x.hypot(y);
+        if mag < self.cfg.deadzone {
+            self.state = SwipeState::Idle;
+            self.elapsed_ms = 0;
+            return None;
+        }
+        match self.state {
+            SwipeState::Locked => return None,
+            // Time counts from the first sample outside the dead zone
+            SwipeState::Idle => self.state = SwipeState::Moving,
+            SwipeState::Moving => self.elapsed_ms += dt_ms,
+        }
+        if self.elapsed_ms > self.cfg.max_ms {
+            self.last = Some(Err(SwipeReject::TooSlow { ms: self.elapsed_ms }));
+            self.state = SwipeState::Locked;
+            return None;
+        }
+        if mag < self.cfg.magnitude { return None; }
+        self.state = SwipeState::Locked;
+        let verdict = self.classifier.classify(x, y);
+        self.last = Some(verdict);
+        verdict.ok().map(|c| c.dir)
+    }
+}
+
+/// Which player slot each gamepad drives. Pads listed in `fixed` keep their slot; others take
+/// the first free of human then AI side (player two in hot-seat) as they connect.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PadSlots {
+    pub fixed: Vec<(usize, Actor)>,
+    /// Connected pads and their slots, in connection order.
+    pub connected: Vec<(usize, Actor)>,
+}
+
+impl PadSlots {
+    pub fn new(fixed: Vec<(usize, Actor)>) -> Self { Self { fixed, connected: Vec::new() } }
+
+    pub fn connect(&mut self, pad: usize) -> Actor {
+        if let Some(actor) = self.actor(pad) { return actor; }
+        let fixed = self.fixed.iter().find(|(p, _)| *p == pad).map(|(_, a)| *a);
+        let taken = |a: Actor| self.connected.iter().any(|(_, c)| *c == a);
+        let actor = fixed.unwrap_or(if taken(Actor::Human) && !taken(Actor::Ai) { Actor::Ai } else { Actor::Human });
+        self.connected.push((pad, actor));
+        actor
+    }
+
+    pub fn disconnect(&mut self, pad: usize) { self.connected.retain(|(p, _)| *p != pad); }
+
+    pub fn actor(&self, pad: usize) -> Option<Actor> {
+        self.connected.iter().find(|(p, _)| *p == pad).map(|(_, a)| *a)
+    }
+}
+
+/// Direction by angle sector without a dead zone; zero motion falls back to `Up`.
+pub fn primary_direction(dx: f32, dy: f32) -> Direction {
+    let classifier = SwipeClassifier { dead_zone_deg: 0.0, ..SwipeClassifier::default() };
+    classifier.classify(dx, dy).map_or(Direction::Up, |c| c.dir)
+}
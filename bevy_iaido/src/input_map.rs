@@ -0,0 +1,115 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::bindings::{InputAction, InputBindings};
+use crate::storage::{load_json, INPUT_MAP_FILE};
+use crate::touch::VirtualKey;
+
+// Names accepted in the input map file, spelled like the enum variants
+macro_rules! by_name {
+    ($fn_name:ident, $ty:ident, [$($v:ident),* $(,)?]) => {
+        fn $fn_name(name: &str) -> Option<$ty> {
+            match name {
+                $(stringify!($v) => Some($ty::$v),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+by_name!(key_code, KeyCode, [
+    KeyA, KeyB, KeyC, KeyD, KeyE, KeyF, KeyG, KeyH, KeyI, KeyJ, KeyK, KeyL, KeyM,
+    KeyN, KeyO, KeyP, KeyQ, KeyR, KeyS, KeyT, KeyU, KeyV, KeyW, KeyX, KeyY, KeyZ,
+    Digit0, Digit1, Digit2, Digit3, Digit4, Digit5, Digit6, Digit7, Digit8, Digit9,
+    Numpad0, Numpad1, Numpad2, Numpad3, Numpad4, Numpad5, Numpad6, Numpad7, Numpad8, Numpad9, NumpadEnter,
+    ArrowUp, ArrowDown, ArrowLeft, ArrowRight, Space, Enter, Escape, Tab, Backspace,
+    ShiftLeft, ShiftRight, ControlLeft, ControlRight, AltLeft, AltRight,
+    Comma, Period, Slash, Semicolon, Quote, BracketLeft, BracketRight, Backslash, Minus, Equal, Backquote,
+    Home, End, PageUp, PageDown, Insert, Delete,
+    F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
+]);
+
+by_name!(pad_button, GamepadButtonType, [
+    South, East, North, West, C, Z, LeftTrigger, LeftTrigger2, RightTrigger, RightTrigger2,
+    Select, Start, Mode, LeftThumb, RightThumb, DPadUp, DPadDown, DPadLeft, DPadRight,
+]);
+
+by_name!(virtual_key, VirtualKey, [Up, Down, Left, Right, Z, X, S, C, Space, Enter, P]);
+
+/// Keys, pad buttons and on-screen buttons for each action, resolved from `InputBindings`.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct InputMap {
+    keys: Vec<(InputAction, KeyCode)>,
+    pad: Vec<(InputAction, GamepadButtonType)>,
+    touch: Vec<(InputAction, VirtualKey)>,
+}
+
+impl InputMap {
+    pub fn from_bindings(bindings: &InputBindings) -> Result<Self, String> {
+        bindings.validate()?;
+        let mut map = Self::default();
+        for b in &bindings.bindings {
+            for name in &b.keys {
+                map.keys.push((b.action, key_code(name).ok_or(format!("unknown key {}", name))?));
+            }
+            for name in &b.pad {
+                map.pad.push((b.action, pad_button(name).ok_or(format!("unknown pad button {}", name))?));
+            }
+            for name in &b.touch {
+                map.touch.push((b.action, virtual_key(name).ok_or(format!("unknown touch button {}", name))?));
+            }
+        }
+        Ok(map)
+    }
+
+    /// `INPUT_MAP_FILE` over the defaults; a file that does not resolve falls back to them.
+    pub fn load() -> Self {
+        let file = load_json::<InputBindings>(INPUT_MAP_FILE).unwrap_or_default();
+        file.over_defaults().and_then(|bindings| Self::from_bindings(&bindings)).unwrap_or_else(|e| {
+            println!("Ignoring {}: {}", INPUT_MAP_FILE, e);
+            Self::from_bindings(&InputBindings::default()).expect("default bindings resolve")
+        })
+    }
+
+    /// Pad buttons for `action`; each pad plays its own side.
+    pub fn pad_buttons(&self, action: InputAction) -> impl Iterator<Item = GamepadButtonType> + '_ {
+        self.pad.iter().filter(move |(a, _)| *a == action).map(|(_, b)| *b)
+    }
+
+    /// First key of `action` for help text, e.g. "Z" or "ArrowLeft".
+    pub fn label(&self, action: InputAction) -> String {
+        match self.keys.iter().find(|(a, _)| *a == action) {
+            Some((_, k)) => {
+                let name = format!("{:?}", k);
+                name.strip_prefix("Key").or(name.strip_prefix("Digit")).unwrap_or(name.as_str()).to_string()
+            }
+            None => "-".to_string(),
+        }
+    }
+}
+
+/// Keys and on-screen buttons, read as actions through the `InputMap`.
+#[derive(SystemParam)]
+pub struct ActionInput<'w> {
+    pub keys: Res<'w, ButtonInput<KeyCode>>,
+    pub vkeys: Res<'w, ButtonInput<VirtualKey>>,
+    pub map: Res<'w, InputMap>,
+}
+
+impl ActionInput<'_> {
+    fn any(&self, action: InputAction, key: impl Fn(KeyCode) -> bool, touch: impl Fn(VirtualKey) -> bool) -> bool {
+        self.map.keys.iter().any(|(a, k)| *a == action && key(*k)) || self.map.touch.iter().any(|(a, v)| *a == action && touch(*v))
+    }
+
+    pub fn pressed(&self, action: InputAction) -> bool {
+        self.any(action, |k| self.keys.pressed(k), |v| self.vkeys.pressed(v))
+    }
+
+    pub fn just_pressed(&self, action: InputAction) -> bool {
+        self.any(action, |k| self.keys.just_pressed(k), |v| self.vkeys.just_pressed(v))
+    }
+
+    pub fn just_released(&self, action: InputAction) -> bool {
+        self.any(action, |k| self.keys.just_released(k), |v| self.vkeys.just_released(v))
+    }
+}
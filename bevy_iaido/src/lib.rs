@@ -6,6 +6,14 @@ mod input;
 mod state_machine;
 mod ai;
 mod logging;
+mod scoring;
+mod training;
+mod storage;
+mod challenge;
+mod tournament;
+mod rating;
+mod calibration;
+mod bindings;
 
 #[cfg(feature = "bevy")]
 mod hud;
@@ -15,6 +23,8 @@ mod plugin;
 mod visuals;
 #[cfg(feature = "bevy")]
 mod touch;
+#[cfg(feature = "bevy")]
+mod input_map;
 
 pub use config::*;
 pub use types::*;
@@ -24,6 +34,14 @@ pub use input::*;
 pub use state_machine::*;
 pub use ai::*;
 pub use logging::*;
+pub use scoring::*;
+pub use training::*;
+pub use storage::*;
+pub use challenge::*;
+pub use tournament::*;
+pub use rating::*;
+pub use calibration::*;
+pub use bindings::*;
 
 #[cfg(feature = "bevy")]
 pub use plugin::*;
@@ -31,9 +49,16 @@ pub use plugin::*;
 pub use visuals::*;
 #[cfg(feature = "bevy")]
 pub use touch::*;
+#[cfg(feature = "bevy")]
+pub use input_map::*;
 
 #[cfg(feature = "bevy")]
 pub fn run_game() {
+    run_game_with(IaidoSettings::default());
+}
+
+#[cfg(feature = "bevy")]
+pub fn run_game_with(settings: IaidoSettings) {
     use bevy::prelude::*;
     let mut window = Window {
         title: "IAIDO MVP".into(),
@@ -53,7 +78,7 @@ pub fn run_game() {
     }
 
     App::new()
-        .insert_resource(IaidoSettings::default())
+        .insert_resource(settings)
         .add_plugins((
             DefaultPlugins
                 .set(WindowPlugin {
@@ -79,35 +104,34 @@ mod tests {
     use super::*;
 
     fn dm_at(now: u64) -> DuelMachine {
-        DuelMachine::new(DuelConfig { seed: 12345, clash: true }, now)
+        DuelMachine::new(DuelConfig { seed: 12345, clash: true, ..DuelConfig::default() }, now)
     }
 
     #[test]
     fn combat_correct_direction_map() {
-        assert_eq!(correct_direction_for(Opening::HighGuard), Direction::Down);
-        assert_eq!(correct_direction_for(Opening::LowGuard), Direction::Up);
-        assert_eq!(correct_direction_for(Opening::LeftGuard), Direction::Right);
-        assert_eq!(correct_direction_for(Opening::RightGuard), Direction::Left);
+        assert_eq!(correct_direction_for(Opening::Down), Direction::Down);
+        assert_eq!(correct_direction_for(Opening::Up), Direction::Up);
+        assert_eq!(correct_direction_for(Opening::Right), Direction::Right);
+        assert_eq!(correct_direction_for(Opening::UpDown), Direction::UpDown);
     }
 
     #[test]
     fn wrong_direction_is_instant_loss() {
-        let opening = Opening::HighGuard; // requires Down
-        let out = judge_outcome(opening, Some(Direction::Up), Some(Direction::Down), Some(50), Some(80), TIE_WINDOW_MS);
+        // Human must answer Down, AI must answer Up
+        let out = judge_outcome(Opening::Down, Opening::Up, Some(Direction::Up), Some(Direction::Up), Some(50), Some(80), TIE_WINDOW_MS);
         assert_eq!(out, Outcome::WrongHuman);
     }
 
     #[test]
     fn faster_reaction_wins() {
-        let opening = Opening::LowGuard; // requires Up
-        let out = judge_outcome(opening, Some(Direction::Up), Some(Direction::Up), Some(90), Some(120), TIE_WINDOW_MS);
+        // Up against Down is neutral, so speed decides
+        let out = judge_outcome(Opening::Up, Opening::Down, Some(Direction::Up), Some(Direction::Down), Some(90), Some(120), TIE_WINDOW_MS);
         assert_eq!(out, Outcome::HumanWin);
     }
 
     #[test]
     fn tie_within_5ms_is_clash() {
-        let opening = Opening::RightGuard; // requires Left
-        let out = judge_outcome(opening, Some(Direction::Left), Some(Direction::Left), Some(100), Some(103), TIE_WINDOW_MS);
+        let out = judge_outcome(Opening::Left, Opening::Right, Some(Direction::Left), Some(Direction::Right), Some(100), Some(103), TIE_WINDOW_MS);
         assert_eq!(out, Outcome::Clash);
     }
 
@@ -123,19 +147,602 @@ mod tests {
     #[test]
     fn clash_reduces_window() {
         let mut dm = dm_at(0);
-        let opening = dm.current_opening();
-        let correct = correct_direction_for(opening);
+        // Neutral pair, both right and within the tie window
+        dm.human_opening = Opening::Left;
+        dm.ai_opening = Opening::Right;
         dm.open_input(1000);
-        dm.on_swipe(Actor::Human, correct, 1100);
-        dm.on_swipe(Actor::Ai, correct, 1103);
+        dm.on_swipe(Actor::Human, Direction::Left, 1100);
+        dm.on_swipe(Actor::Ai, Direction::Right, 1103);
         dm.tick(1200); // resolve
         assert!(matches!(dm.round_results.last().unwrap().outcome, Outcome::Clash));
         assert_eq!(dm.input_window_ms, CLASH_INPUT_WINDOW_MS);
     }
 
+    #[test]
+    fn swipe_on_decoy_is_early() {
+        let mut dm = DuelMachine::new(DuelConfig { seed: 12345, decoys: true, ..DuelConfig::default() }, 0);
+        let mut now = 0;
+        dm.start_round(now);
+        while dm.pending_decoy.is_none() {
+            now += 10_000;
+            dm.start_round(now);
+        }
+        let decoy = dm.pending_decoy.clone().unwrap();
+        assert!(decoy.ts_ms + DECOY_MIN_LEAD_MS <= dm.delay_target_ms.unwrap());
+        dm.tick(decoy.ts_ms);
+        assert_eq!(dm.take_fired_decoy(), Some(decoy.clone()));
+        assert_eq!(dm.phase, DuelPhase::RandomDelay);
+        dm.on_swipe(Actor::Human, Direction::Up, decoy.ts_ms + 30);
+        assert!(matches!(dm.round_results.last().unwrap().outcome, Outcome::EarlyHuman));
+    }
+
+    #[test]
+    fn memory_reveal_hides_opening_until_go() {
+        let mut dm = DuelMachine::new(DuelConfig { seed: 12345, reveal: RevealMode::Memory, ..DuelConfig::default() }, 0);
+        // The flash plays during Standoff and shows the openings that are then fought
+        assert!(dm.opening_visible(MEMORY_FLASH_MS - 1));
+        assert!(!dm.opening_visible(MEMORY_FLASH_MS));
+        let shown = (dm.human_opening, dm.ai_opening);
+        dm.tick(START_DELAY_MS);
+        assert_eq!(dm.phase, DuelPhase::RandomDelay);
+        assert!(!dm.opening_visible(START_DELAY_MS));
+        assert_eq!((dm.human_opening, dm.ai_opening), shown);
+        let go = START_DELAY_MS + 2000;
+        dm.open_input(go);
+        assert!(dm.opening_visible(go));
+        dm.on_swipe(Actor::Human, correct_direction_for(dm.human_opening), go + 150);
+        dm.tick(go + 700);
+        assert_eq!(dm.round_results[0].human_opening, shown.0);
+
+        // Later rounds pause in Standoff again so the next opening can flash
+        dm.tick(go + 1000);
+        dm.tick(go + 1500);
+        assert_eq!(dm.phase, DuelPhase::Standoff);
+        assert!(dm.opening_visible(go + 1500 + MEMORY_FLASH_MS - 1));
+        assert!(!dm.opening_visible(go + 1500 + MEMORY_FLASH_MS));
+        let shown = dm.human_opening;
+        dm.tick(go + 1500 + MEMORY_STANDOFF_MS);
+        assert_eq!(dm.phase, DuelPhase::RandomDelay);
+        assert_eq!(dm.human_opening, shown);
+    }
+
+    #[test]
+    fn combo_round_records_and_replays_every_step() {
+        let mut dm = DuelMachine::new(DuelConfig { seed: 12345, combo_len: 3, ..DuelConfig::default() }, 0);
+        assert_eq!(dm.human_combo.len(), 3);
+        let go = 1000;
+        dm.open_input(go);
+        for (i, ts) in [go + 150, go + 300, go + 420].into_iter().enumerate() {
+            dm.on_swipe(Actor::Human, correct_direction_for(dm.human_combo[i]), ts);
+            dm.on_swipe(Actor::Ai, correct_direction_for(dm.ai_combo[i]), ts + 40);
+        }
+        dm.tick(go + 500);
+        assert_eq!(dm.round_results.last().unwrap().outcome, Outcome::HumanWin);
+        let log = dm.last_duel_log().expect("has log");
+        assert_eq!(log.human_steps.len(), 3);
+        replay_round(&DuelLog::from_json(&log.to_json()).unwrap()).expect("replay matches");
+        // The next round rolls new combos; the finished round's log keeps its own
+        dm.tick(go + 900);
+        dm.tick(go + 1500);
+        assert_eq!(dm.phase, DuelPhase::RandomDelay);
+        let again = dm.last_duel_log().unwrap();
+        assert_eq!((&again.human_combo, &again.ai_combo), (&log.human_combo, &log.ai_combo));
+        replay_round(&again).expect("replay matches");
+    }
+
+    #[test]
+    fn struggle_meter_decides_clash() {
+        let mut dm = DuelMachine::new(DuelConfig { seed: 12345, struggle: true, format: MatchFormat::Rounds(2), ..DuelConfig::default() }, 0);
+        dm.open_input(1000);
+        dm.tick(1000 + INPUT_WINDOW_MS + 1); // nobody swiped: clash
+        assert_eq!(dm.phase, DuelPhase::Struggle);
+        let start = dm.phase_start_ms;
+        for i in 0..5 {
+            dm.on_struggle_input(Actor::Human, start + 100 * i);
+            dm.on_struggle_input(Actor::Ai, start + 100 * i + 50);
+        }
+        dm.on_struggle_input(Actor::Ai, start + 900);
+        dm.tick(start + STRUGGLE_MS);
+        let last = dm.round_results.last().unwrap();
+        assert_eq!((last.outcome, last.struggle_meter), (Outcome::AiWin, Some(-1)));
+        assert_eq!(dm.ai_score, 1);
+        // The lock settles the clash in place: still one round of two played
+        assert_eq!((dm.round_results.len(), dm.round_meta.len()), (1, 1));
+        assert_eq!(dm.match_state, MatchState::InProgress);
+        let log = dm.last_duel_log().expect("has log");
+        replay_round(&log).expect("replay matches");
+    }
+
+    #[test]
+    fn broken_posture_shrinks_next_window() {
+        let mut dm = DuelMachine::new(DuelConfig { seed: 12345, posture: true, ..DuelConfig::default() }, 0);
+        let mut go = 1000;
+        // Three reckless wrong swipes drain 40 each
+        for _ in 0..3 {
+            dm.open_input(go);
+            let wrong = if correct_direction_for(dm.human_opening) == Direction::Up { Direction::Down } else { Direction::Up };
+            dm.on_swipe(Actor::Human, wrong, go + 100);
+            dm.tick(go + INPUT_WINDOW_MS + 1);
+            go += 2000;
+        }
+        let last = dm.round_results.last().unwrap();
+        assert_eq!(last.outcome, Outcome::WrongHuman);
+        assert!(last.posture.unwrap().human_broken);
+        assert_eq!(dm.input_window_for(Actor::Human), INPUT_WINDOW_MS - POSTURE_BROKEN_WINDOW_CUT_MS);
+        assert_eq!(dm.input_window_for(Actor::Ai), INPUT_WINDOW_MS);
+        dm.open_input(go);
+        dm.on_swipe(Actor::Human, correct_direction_for(dm.human_opening), go + 450);
+        assert!(dm.human_swipe.is_none());
+    }
+
+    #[test]
+    fn draw_then_cut_scores_combined_time() {
+        let mut dm = DuelMachine::new(DuelConfig { seed: 12345, draw_cut: true, ..DuelConfig::default() }, 0);
+        let go = 1000;
+        dm.open_draw(go);
+        // Fast draw but slow cut loses to a steady draw and quick cut
+        dm.on_swipe(Actor::Human, Direction::Left, go + 100);
+        dm.on_swipe(Actor::Ai, Direction::Left, go + 200);
+        dm.tick(go + 200);
+        assert_eq!(dm.phase, DuelPhase::CutDelay);
+        let cut = dm.delay_target_ms.unwrap();
+        assert!(cut - (go + 200) >= CUT_DELAY_MIN_MS);
+        dm.tick(cut);
+        assert_eq!(dm.phase, DuelPhase::CutSignal);
+        dm.tick(cut);
+        dm.on_swipe(Actor::Human, correct_direction_for(dm.human_opening), cut + 250);
+        dm.on_swipe(Actor::Ai, correct_direction_for(dm.ai_opening), cut + 100);
+        dm.tick(cut + INPUT_WINDOW_MS + 1);
+        let last = dm.round_results.last().unwrap();
+        assert_eq!((last.outcome, last.human_draw_ms, last.ai_draw_ms), (Outcome::AiWin, Some(100), Some(200)));
+        replay_round(&dm.last_duel_log().expect("has log")).expect("replay matches");
+
+        // Swiping again before the cut cue is a false start of the cut stage
+        dm.open_draw(5000);
+        dm.on_swipe(Actor::Human, Direction::Up, 5100);
+        dm.on_swipe(Actor::Ai, Direction::Up, 5150);
+        dm.tick(5150);
+        dm.on_swipe(Actor::Human, Direction::Up, 5200);
+        let last = dm.round_results.last().unwrap();
+        assert_eq!((last.outcome, last.stage), (Outcome::EarlyHuman, Some(DrawCutStage::Cut)));
+    }
+
+    #[test]
+    fn style_score_grades_wins_and_builds_streak() {
+        let mut dm = dm_at(0);
+        // Round 1: 150ms vs 200ms is S with a 50ms margin; round 2: 300ms vs 400ms bumps B to A
+        for (go, human, ai) in [(1000, 150, 200), (5000, 300, 400)] {
+            dm.open_input(go);
+            dm.on_swipe(Actor::Human, correct_direction_for(dm.human_opening), go + human);
+            dm.on_swipe(Actor::Ai, correct_direction_for(dm.ai_opening), go + ai);
+            dm.tick(go + INPUT_WINDOW_MS + 1);
+        }
+        let score = score_match(&dm.round_results);
+        let r1 = score.rounds[0].unwrap();
+        let r2 = score.rounds[1].unwrap();
+        assert_eq!((r1.grade, r1.margin_ms, r1.points), (Grade::S, 50, 116));
+        assert_eq!((r2.grade, r2.streak, r2.points), (Grade::A, 2, (75 + 33) * 125 / 100));
+        assert_eq!((score.total, score.best_streak), (116 + 135, 2));
+        let log = MatchLog::new(dm.seed, Vec::new(), &dm.round_results);
+        assert_eq!(MatchLog::from_json(&log.to_json()).unwrap().score, Some(score));
+    }
+
+    #[test]
+    fn handicap_forgives_one_wrong_and_offsets_speed() {
+        let weak = Handicap { extra_window_ms: 100, reaction_offset_ms: 60, forgive_wrong: true, ..Handicap::default() };
+        // Pick a seed whose openings go to the speed check (neither side parries nor outranks)
+        let mut dm = (1..)
+            .map(|seed| DuelMachine::new(DuelConfig { seed, human_handicap: weak, ..DuelConfig::default() }, 0))
+            .find(|dm| {
+                let (h, a) = (correct_direction_for(dm.human_opening), correct_direction_for(dm.ai_opening));
+                judge_outcome(dm.human_opening, dm.ai_opening, Some(h), Some(a), Some(100), Some(200), TIE_WINDOW_MS) == Outcome::HumanWin
+                    && judge_outcome(dm.human_opening, dm.ai_opening, Some(h), Some(a), Some(200), Some(100), TIE_WINDOW_MS) == Outcome::AiWin
+            })
+            .unwrap();
+        assert_eq!(dm.input_window_for(Actor::Human), INPUT_WINDOW_MS + 100);
+        let go = 1000;
+        dm.open_input(go);
+        let correct = correct_direction_for(dm.human_opening);
+        let wrong = if correct == Direction::Up { Direction::Down } else { Direction::Up };
+        dm.on_swipe(Actor::Human, wrong, go + 100);
+        dm.on_swipe(Actor::Human, correct, go + 250);
+        dm.on_swipe(Actor::Ai, correct_direction_for(dm.ai_opening), go + 200);
+        dm.tick(go + INPUT_WINDOW_MS + 101);
+        // 250 - 60 beats 200, and the first wrong swipe never counted
+        assert_eq!(dm.round_results.last().unwrap().outcome, Outcome::HumanWin);
+        assert_eq!(dm.human_forgives, 0);
+        let log = dm.last_duel_log().expect("has log");
+        assert_eq!((log.human_forgives, log.human_handicap), (1, weak));
+        replay_round(&DuelLog::from_json(&log.to_json()).unwrap()).expect("replay matches");
+    }
+
+    #[test]
+    fn reactions_count_from_the_presented_go() {
+        let mut dm = (1..)
+            .map(|seed| DuelMachine::new(DuelConfig { seed, ..DuelConfig::default() }, 0))
+            .find(|dm| {
+                let (h, a) = (correct_direction_for(dm.human_opening), correct_direction_for(dm.ai_opening));
+                judge_outcome(dm.human_opening, dm.ai_opening, Some(h), Some(a), Some(100), Some(200), TIE_WINDOW_MS) == Outcome::HumanWin
+            })
+            .unwrap();
+        let go = 1000;
+        dm.open_input(go);
+        // Shown 16.6 ms after GO, sound 30 ms behind that: the human reacts 250 - 46.6 ms after the cue
+        let cue = GoPresentation { presented_us: go * 1000 + 16_600, audio_latency_ms: 30 };
+        dm.present_go(cue);
+        dm.present_go(GoPresentation { presented_us: go * 1000 + 40_000, audio_latency_ms: 0 }); // first stamp wins
+        dm.on_swipe_us(Actor::Human, correct_direction_for(dm.human_opening), (go + 250) * 1000);
+        dm.on_swipe_us(Actor::Ai, correct_direction_for(dm.ai_opening), (go + 220) * 1000);
+        dm.tick(go + INPUT_WINDOW_MS + 1);
+        let last = dm.round_results.last().unwrap();
+        assert_eq!(last.outcome, Outcome::HumanWin);
+        assert_eq!((last.human_reaction_us, last.human_reaction_ms), (Some(203_400), Some(203)));
+        assert_eq!(last.ai_reaction_us, Some(220_000)); // the AI reads GO off the machine
+        let log = DuelLog::from_json(&dm.last_duel_log().unwrap().to_json()).unwrap();
+        assert_eq!(log.go.presented, Some(cue));
+        replay_round(&log).expect("replay applies the presentation");
+    }
+
+    #[test]
+    fn moving_before_the_cue_arrives_is_early() {
+        let mut dm = dm_at(0);
+        let go = 1000;
+        dm.open_input(go);
+        dm.present_go(GoPresentation { presented_us: go * 1000 + 16_600, audio_latency_ms: 0 });
+        // After the GO tick but 6.6 ms before the cue was on screen: anticipated, not a 0 ms reaction
+        dm.on_swipe(Actor::Human, correct_direction_for(dm.human_opening), go + 10);
+        dm.on_swipe(Actor::Ai, correct_direction_for(dm.ai_opening), go + 200);
+        dm.tick(go + INPUT_WINDOW_MS + 1);
+        let last = dm.round_results.last().unwrap();
+        assert_eq!(last.outcome, Outcome::EarlyHuman);
+        assert_eq!((last.human_reaction_ms, last.human_reaction_us), (None, None));
+        assert_eq!(dm.ai_score, 1);
+        replay_round(&DuelLog::from_json(&dm.last_duel_log().unwrap().to_json()).unwrap()).expect("replay matches");
+    }
+
+    #[test]
+    fn calibration_estimates_offset_and_corrects_reactions() {
+        let mut cal = LatencyCalibration::new(CALIBRATION_BEAT_MS);
+        let beat_us = CALIBRATION_BEAT_MS * 1000;
+        let mut counted = 0;
+        for i in 0..(CALIBRATION_WARMUP_BEATS + CALIBRATION_TRIALS) as u64 {
+            let at = 1_000_000 + i * beat_us;
+            cal.beat(at);
+            assert_eq!(cal.tap(at - beat_us / 2 - 1), None); // closer to no beat than half a beat
+            // 30 ms late, alternating ±5 ms
+            let late = if i.is_multiple_of(2) { 35_000 } else { 25_000 };
+            if cal.tap(at + late).is_some() { counted += 1; }
+            assert_eq!(cal.tap(at + 50_000), None); // one tap per beat
+        }
+        assert_eq!(counted, CALIBRATION_TRIALS);
+        assert!(cal.is_done());
+        let est = cal.estimate().unwrap();
+        assert_eq!((est.offset_us, est.jitter_us, est.trials), (30_000, 5_000, CALIBRATION_TRIALS as u32));
+        assert_eq!(LatencyEstimate { offset_us: -20_000, ..est }.correction_us(), 0);
+        assert_eq!(LatencyEstimate { offset_us: 900_000, ..est }.correction_us(), CALIBRATION_MAX_CORRECTION_MS * 1000);
+
+        let mut dm = dm_at(0);
+        dm.input_offset_us = DeviceProfile { latency: Some(est), ..DeviceProfile::default() }.input_offset_us();
+        let go = 1000;
+        dm.open_input(go);
+        dm.on_swipe_us(Actor::Human, correct_direction_for(dm.human_opening), (go + 250) * 1000);
+        dm.on_swipe_us(Actor::Ai, correct_direction_for(dm.ai_opening), (go + 250) * 1000);
+        dm.tick(go + INPUT_WINDOW_MS + 1);
+        let last = dm.round_results.last().unwrap();
+        assert_eq!((last.human_reaction_us, last.ai_reaction_us), (Some(220_000), Some(250_000)));
+        let log = DuelLog::from_json(&dm.last_duel_log().unwrap().to_json()).unwrap();
+        assert_eq!(log.input_offset_us, 30_000);
+        replay_round(&log).expect("replay applies the correction");
+    }
+
+    #[test]
+    fn late_calibration_cannot_buy_a_win() {
+        let run = |late: &dyn Fn(u64) -> u64| {
+            let mut cal = LatencyCalibration::new(CALIBRATION_BEAT_MS);
+            for i in 0..(CALIBRATION_WARMUP_BEATS + CALIBRATION_TRIALS) as u64 {
+                let at = 1_000_000 + i * CALIBRATION_BEAT_MS * 1000;
+                cal.beat(at);
+                cal.tap(at + late(i));
+            }
+            cal.estimate()
+        };
+        // Scattered taps are rejected outright
+        assert_eq!(run(&|i| if i.is_multiple_of(2) { 0 } else { 100_000 }), None);
+        // Steady taps 300 ms behind the beat are believed only up to a device's latency
+        let device = DeviceProfile { latency: run(&|_| 300_000), ..DeviceProfile::default() };
+        assert_eq!(device.input_offset_us(), CALIBRATION_MAX_CORRECTION_MS * 1000);
+
+        // 50 ms slower than the AI: the capped correction is not enough to win
+        let outcome = |offset_us| {
+            let mut dm = dm_at(0);
+            dm.input_offset_us = offset_us;
+            let go = 1000;
+            dm.open_input(go);
+            dm.on_swipe(Actor::Human, correct_direction_for(dm.human_opening), go + 260);
+            dm.on_swipe(Actor::Ai, correct_direction_for(dm.ai_opening), go + 210);
+            dm.tick(go + INPUT_WINDOW_MS + 1);
+            dm.round_results.last().unwrap().outcome
+        };
+        assert_eq!(outcome(device.input_offset_us()), Outcome::AiWin);
+        assert_eq!(outcome(60_000), Outcome::HumanWin); // what an uncapped 60 ms would buy
+    }
+
+    #[test]
+    fn swipe_calibration_fits_distance_and_lock_and_dpi_is_detected() {
+        assert_eq!(detect_dpi(2.0, None), REFERENCE_DPI);
+        // 1080 physical px across 68.58 mm is 400 dpi; at scale 2.5 that is 160 per logical px
+        assert!((detect_dpi(2.5, Some((1080.0, 68.58))) - 160.0).abs() < 0.01);
+
+        let mut cal = SwipeCalibration::new(254.0); // 10 px per mm
+        cal.motion(16, 10.0, 0.0);
+        assert_eq!(cal.release(), None); // 1 mm is a tap
+        for (mm, frames) in [(16.0, 6), (20.0, 8), (24.0, 10), (18.0, 6), (22.0, 9)] {
+            for _ in 0..frames { cal.motion(10, mm * 10.0 / frames as f32, 0.0); }
+            cal.release().unwrap();
+        }
+        assert!(cal.is_done());
+        // Median 20 mm over 70 ms (timed from the first moving frame)
+        let est = cal.estimate().unwrap();
+        assert_eq!((est.min_swipe_mm, est.lock_ms, est.swipes), (10.0, 14, 5));
+        let swipe_cfg = DeviceProfile { swipe: Some(est), ..DeviceProfile::default() }.swipe_config(254.0);
+        assert!((swipe_cfg.min_distance_px() - 100.0).abs() < 0.01);
+
+        let mut sd = SwipeDetector::new();
+        let step = |sd: &mut SwipeDetector| sd.update(&swipe_cfg, SwipeSample { dt_ms: 7, dx: 30.0, dy: 0.0 });
+        let fired: Vec<_> = (0..5).map(|_| step(&mut sd)).collect();
+        // Locks at 14 ms, fires once past 100 px
+        assert_eq!(fired, vec![None, None, None, None, Some(Direction::Right)]);
+    }
+
+    #[test]
+    fn training_drills_selected_openings_and_reports_slowest() {
+        let drill = vec!["updown".parse().unwrap(), Opening::Left];
+        let cfg = DuelConfig { seed: 7, clash: false, format: MatchFormat::Rounds(6), drill: drill.clone(), ..DuelConfig::default() };
+        let mut dm = DuelMachine::new(cfg, 0);
+        let mut go = 1000;
+        for i in 0..6 {
+            assert!(drill.contains(&dm.human_opening));
+            dm.open_input(go);
+            // Solo: answer UpDown slowly, Left quickly, miss the last rep entirely
+            let react = if dm.human_opening == Opening::UpDown { 400 } else { 200 };
+            if i < 5 { dm.on_swipe(Actor::Human, correct_direction_for(dm.human_opening), go + react); }
+            dm.tick(go + INPUT_WINDOW_MS + 1);
+            go += 3000;
+            dm.tick(go - 1000);
+            dm.tick(go - 500);
+        }
+        assert_eq!(dm.match_state, MatchState::Finished);
+        let report = training_report(&dm.round_results);
+        assert_eq!((report.hits(), report.attempts()), (5, 6));
+        let slowest = report.slowest();
+        assert_eq!(slowest.len(), 2);
+        assert_eq!((slowest[0].opening, slowest[0].avg_ms()), (Opening::UpDown, Some(400)));
+    }
+
+    #[test]
+    fn match_log_replays_every_round_and_drives_a_ghost() {
+        let mut dm = dm_at(0);
+        let mut go = 1000;
+        // Round 1 early, then two clean wins
+        dm.start_round(0);
+        dm.on_swipe(Actor::Human, Direction::Up, 10);
+        for react in [180, 240] {
+            dm.open_input(go);
+            dm.on_swipe(Actor::Human, correct_direction_for(dm.human_opening), go + react);
+            dm.tick(go + INPUT_WINDOW_MS + 1);
+            go += 3000;
+            dm.tick(go - 2000);
+            dm.tick(go - 1000);
+        }
+        let log = MatchLog::from_json(&dm.match_log().to_json()).unwrap();
+        assert_eq!(log.rounds.len(), 3);
+        assert!(replay_match(&log));
+
+        let ghost = Ghost::new(log);
+        assert!(ghost.plan(0, 0).is_none()); // false starts are not copied
+        let plan = ghost.plan(2, 0).unwrap();
+        assert_eq!((plan.reaction_ms, plan.wrong), (240, false));
+        assert_eq!(ghost.reaction_ms(1), Some(180));
+        assert!(ghost.plan(3, 0).is_none());
+    }
+
+    #[test]
+    fn survival_tightens_each_round_until_first_loss() {
+        let schedule = SurvivalSchedule { window_step_ms: 100, window_min_ms: 400, ..SurvivalSchedule::default() };
+        let cfg = DuelConfig { seed: 3, format: MatchFormat::Survival, survival: schedule, ..DuelConfig::default() };
+        let mut dm = DuelMachine::new(cfg, 0);
+        let mut now = 0;
+        dm.start_round(now);
+        let mut windows = Vec::new();
+        for _ in 0..4 {
+            windows.push((dm.input_window_ms, dm.tie_window_ms));
+            let go = dm.delay_target_ms.unwrap();
+            dm.tick(go);
+            dm.tick(go);
+            dm.on_swipe(Actor::Human, correct_direction_for(dm.human_opening), go + 100);
+            now = go + dm.input_window_ms + 1;
+            dm.tick(now);
+            dm.tick(now + 300);
+            dm.tick(now + 800);
+        }
+        assert_eq!(windows, vec![(600, 20), (500, 19), (400, 18), (400, 17)]);
+        assert_eq!(dm.survival_round(), 4);
+        assert_eq!(schedule.ai_reaction_at(350, 4), 310);
+        // One loss ends the run
+        let go = dm.delay_target_ms.unwrap();
+        dm.tick(go);
+        dm.on_swipe(Actor::Human, Direction::Up, go - 10);
+        assert_eq!(dm.match_state, MatchState::Finished);
+        assert_eq!(dm.human_score, 4);
+    }
+
+    #[test]
+    fn survival_streak_runs_past_the_score_cap() {
+        let mut dm = DuelMachine::new(DuelConfig { seed: 3, format: MatchFormat::Survival, ..DuelConfig::default() }, 0);
+        dm.start_round(0);
+        for _ in 0..300 {
+            let go = dm.delay_target_ms.unwrap();
+            dm.tick(go);
+            dm.tick(go);
+            dm.on_swipe(Actor::Human, correct_direction_for(dm.human_opening), go + 100);
+            let now = go + dm.input_window_ms + 1;
+            dm.tick(now);
+            dm.tick(now + 300);
+            dm.tick(now + 800);
+        }
+        assert_eq!(dm.match_state, MatchState::InProgress);
+        assert_eq!((dm.survival_round(), dm.human_score), (300, u8::MAX));
+    }
+
+    #[test]
+    fn daily_challenge_is_fixed_by_date() {
+        let date: Date = "2026-10-18".parse().unwrap();
+        assert_eq!(Date::from_days(date.days()), date);
+        assert_eq!(Date::from_days(0).to_string(), "1970-01-01");
+        assert_eq!(date.weekday(), 6); // Sunday
+        assert!("2026-02-30".parse::<Date>().is_err());
+
+        let a = DailyChallenge::for_date(date);
+        let b = DailyChallenge::for_date(date);
+        assert_eq!(a, b);
+        assert_eq!(a.code(), b.code());
+        assert_eq!(Challenge::from_code(&a.code()).unwrap(), a.challenge());
+        assert_eq!(a.ai, AiTier::Master);
+        assert_eq!(a.duel_config().seed, a.seed);
+        let next = DailyChallenge::for_date(Date::from_days(date.days() + 1));
+        assert_ne!(next.seed, a.seed);
+        assert_ne!(next.code(), a.code());
+
+        // One scored attempt per day
+        let mut history = DailyHistory::default();
+        let result = DailyResult { date, code: a.code(), human_score: 2, ai_score: 1, score: 900, forfeit: false };
+        assert!(history.record(result.clone()));
+        assert!(!history.record(DailyResult { score: 2000, ..result.clone() }));
+        assert_eq!(history.results[0].score, 900);
+        assert!(!history.settle(DailyResult { score: 2000, ..result }));
+
+        // The attempt is claimed when it starts: quitting leaves a forfeit, finishing replaces it
+        let mut history = DailyHistory::default();
+        assert!(history.begin(date, a.code()));
+        assert!(history.played(date) && history.results[0].forfeit);
+        assert!(!history.begin(date, a.code()));
+        assert!(history.settle(DailyResult { date, code: a.code(), human_score: 2, ai_score: 0, score: 700, forfeit: false }));
+        assert_eq!((history.results.len(), history.results[0].score, history.results[0].forfeit), (1, 700, false));
+        assert!(!history.settle(DailyResult { date, code: a.code(), human_score: 2, ai_score: 0, score: 2000, forfeit: false }));
+    }
+
+    #[test]
+    fn challenge_code_round_trips_and_rejects_typos() {
+        let cfg = DuelConfig {
+            seed: 0xC0FFEE,
+            decoys: true,
+            reveal: RevealMode::Memory,
+            combo_len: 3,
+            combo_timing: ComboTiming::PerStep,
+            format: MatchFormat::Survival,
+            survival: SurvivalSchedule { window_step_ms: 40, ..SurvivalSchedule::default() },
+            human_handicap: Handicap { extra_window_ms: 50, forgive_wrong: true, ..Handicap::default() },
+            ..DuelConfig::default()
+        };
+        let challenge = Challenge::from_config(&cfg, AiTier::Skilled);
+        let code = challenge.code();
+        assert!(code.chars().all(|c| c == '-' || c.is_ascii_alphanumeric()));
+        let back: Challenge = code.to_lowercase().parse().unwrap();
+        assert_eq!(back, challenge);
+        let played = back.duel_config();
+        assert_eq!((played.seed, played.combo_len, played.format), (0xC0FFEE, 3, MatchFormat::Survival));
+        assert_eq!(played.survival.window_step_ms, 40);
+        assert_eq!(played.human_handicap.extra_window_ms, 50);
+
+        // A plain best-of-three stays short
+        let plain = Challenge::from_config(&DuelConfig::default(), AiTier::Dumb).code();
+        assert!(plain.len() <= 23);
+        // Any single-character typo is caught, at every position and with every other character
+        for (i, c) in code.char_indices().filter(|(_, c)| *c != '-') {
+            for sub in "0123456789ABCDEFGHJKMNPQRSTVWXYZ".chars().filter(|s| *s != c) {
+                let typo = format!("{}{}{}", &code[..i], sub, &code[i + 1..]);
+                assert!(Challenge::from_code(&typo).is_err(), "typo {} at {} not caught", sub, i);
+            }
+        }
+        assert!(Challenge::from_code("U").is_err());
+
+        // Exports only compare on the same code
+        let log = |total| MatchLog { seed: cfg.seed, rounds: Vec::new(), score: Some(MatchScore { rounds: Vec::new(), total, best_streak: 0 }) };
+        let mine = ChallengeExport { code: code.clone(), log: log(900) };
+        let theirs = ChallengeExport { code: code.to_lowercase(), log: log(700) };
+        assert_eq!(mine.beats(&theirs), Some(true));
+        assert_eq!(mine.beats(&ChallengeExport { code: plain, log: log(0) }), None);
+    }
+
+    #[test]
+    fn tournament_brackets_resolve_byes_and_resume() {
+        // Five entrants: top seeds get byes, the two humans meet in a hot-seat match
+        let entrants: Vec<Entrant> = ["ai:master", "alice", "ai:novice", "bob", "ai:dumb"]
+            .iter().map(|s| s.parse().unwrap()).collect();
+        let mut single = Tournament::new(BracketKind::SingleElimination, entrants.clone(), 7, 2).unwrap();
+        assert_eq!(single.matches.len(), 7);
+        assert_eq!(single.decided(0), Some((Slot::Entrant(0), Slot::Bye)));
+        assert_eq!(single.next_match(), Some(1)); // seed 4 (bob) vs seed 5 (dumb AI)
+        assert_eq!(single.simulate_ai_matches(), 0);
+        single.report(1, 2, 0).unwrap();
+        assert!(single.report(1, 2, 0).is_err());
+
+        // JSON round trip keeps every result
+        let json = serde_json::to_string(&single).unwrap();
+        let mut resumed: Tournament = serde_json::from_str(&json).unwrap();
+        assert_eq!(resumed, single);
+        resumed.simulate_ai_matches();
+        while let Some(id) = resumed.next_match() {
+            resumed.report(id, 0, 2).unwrap();
+            resumed.simulate_ai_matches();
+        }
+        let champ = resumed.champion().unwrap();
+        assert_eq!(resumed.winner(resumed.matches.len() - 1), Some(champ));
+
+        // Double elimination: everyone but the champion loses twice, or once in the final
+        let mut double = Tournament::new(BracketKind::DoubleElimination, vec![Entrant::ai(AiTier::Master), Entrant::ai(AiTier::Dumb), Entrant::ai(AiTier::Novice), Entrant::ai(AiTier::Skilled)], 9, 1).unwrap();
+        assert_eq!(double.matches.len(), 6);
+        assert_eq!(double.matches.last().unwrap().bracket, Bracket::GrandFinal);
+        assert_eq!(double.simulate_ai_matches(), double.matches.len());
+        assert!(double.champion().is_some());
+
+        // Headless matches finish on their own
+        let dm = simulate_match(DuelConfig { seed: 11, ..DuelConfig::default() }, MASTER, DUMB);
+        assert_eq!(dm.match_state, MatchState::HumanWon);
+
+        // Round robin: everyone meets everyone once
+        let mut league = Tournament::new(BracketKind::RoundRobin, vec![Entrant::ai(AiTier::Master), Entrant::ai(AiTier::Dumb), Entrant::ai(AiTier::Novice)], 3, 2).unwrap();
+        assert_eq!(league.matches.len(), 3);
+        league.simulate_ai_matches();
+        let table = league.standings();
+        assert_eq!(table.iter().map(|s| s.played).sum::<u32>(), 6);
+        assert_eq!(league.champion(), Some(table[0].entrant));
+        assert!(Tournament::new(BracketKind::RoundRobin, vec![Entrant::human("solo")], 0, 2).is_err());
+    }
+
+    #[test]
+    fn rating_moves_by_expected_score_against_ai_tiers() {
+        let mut profile = PlayerProfile::default();
+        assert_eq!(profile.rating, RATING_START);
+        // Beating an equal opponent while provisional: half of K
+        let entry = profile.record(NOVICE.rating, true, 0);
+        assert_eq!(entry.delta(), RATING_K_PROVISIONAL / 2);
+        // Losing to a much stronger tier costs little, beating it gains a lot
+        let loss = profile.record(MASTER.rating, false, 0).delta();
+        let win = profile.record(MASTER.rating, true, 0).delta();
+        assert!(loss > -5 && win > 35, "{} {}", loss, win);
+        assert_eq!((profile.games, profile.wins, profile.history.len()), (3, 2, 3));
+        // Settles to the regular K after the provisional games
+        for _ in 0..RATING_PROVISIONAL_GAMES { profile.record(DUMB.rating, true, 0); }
+        assert_eq!(profile.k_factor(), RATING_K);
+        assert_eq!(expected_score(1000, 1000), 0.5);
+        let json = serde_json::to_string(&profile).unwrap();
+        assert_eq!(serde_json::from_str::<PlayerProfile>(&json).unwrap(), profile);
+    }
+
     #[test]
     fn swipe_detector_locks_and_threshold() {
-        let cfg = SwipeConfig { dpi: 320.0 };
+        let cfg = SwipeConfig::new(320.0);
         let mut sd = SwipeDetector::new();
         // Move for 25ms to lock direction, accumulate to exceed 7mm threshold (~88 px at 320dpi)
         let mut dir = None;
@@ -149,6 +756,116 @@ mod tests {
         assert_eq!(dir, Some(Direction::Right));
     }
 
+    #[test]
+    fn swipe_times_are_interpolated_and_kept_in_microseconds() {
+        let cfg = SwipeConfig::new(320.0);
+        let mut sd = SwipeDetector::new();
+        let sample = |dx| SwipeSample { dt_ms: 10, dx, dy: 0.0 };
+        for ts in [0, 10_000, 20_000] { assert_eq!(sd.update_at(&cfg, sample(40.0), ts), None); }
+        // 80 px at 20 ms, 140 px at 30 ms: the ~88 px threshold falls ~1.4 ms into the frame
+        let (dir, at) = sd.update_at(&cfg, sample(60.0), 30_000).unwrap();
+        assert_eq!(dir, Direction::Right);
+        assert!((21_000..22_000).contains(&at), "crossed at {at}");
+
+        // 5.2 ms apart beats the 5 ms tie window once sub-millisecond parts count
+        assert_eq!(speed_outcome_us(100_400, 105_600, TIE_WINDOW_MS, SpeedCheck::default()), Outcome::HumanWin);
+        assert_eq!(speed_outcome_us(100_400, 105_300, TIE_WINDOW_MS, SpeedCheck::default()), Outcome::Clash);
+
+        let mut dm = dm_at(0);
+        let go = 1000;
+        dm.open_input(go);
+        dm.on_swipe_us(Actor::Human, correct_direction_for(dm.human_opening), go * 1000 + 150_250);
+        dm.on_swipe(Actor::Ai, correct_direction_for(dm.ai_opening), go + 200);
+        dm.tick(go + INPUT_WINDOW_MS + 1);
+        let last = dm.round_results.last().unwrap();
+        assert_eq!((last.human_reaction_us, last.human_reaction_ms), (Some(150_250), Some(150)));
+        // A frame-timed input claims no more than the millisecond
+        assert_eq!((last.ai_reaction_us, last.ai_reaction_ms), (None, Some(200)));
+        let log = dm.last_duel_log().unwrap();
+        assert_eq!(log.human.as_ref().map(|e| e.frac_us), Some(Some(250)));
+        assert_eq!(log.ai.as_ref().map(|e| e.frac_us), Some(None));
+        replay_round(&log).expect("replay keeps sub-millisecond times");
+    }
+
+    #[test]
+    fn swipe_classifier_sectors_dead_zones_and_confidence() {
+        let c = SwipeClassifier::default();
+        let at = |deg: f32| c.classify(deg.to_radians().cos(), deg.to_radians().sin());
+        assert_eq!(at(0.0).unwrap(), Classified { dir: Direction::Right, confidence: 1.0 });
+        assert_eq!(at(90.0).unwrap().dir, Direction::Up);
+        assert_eq!(at(225.0).unwrap().dir, Direction::DownLeft);
+        assert_eq!(at(350.0).unwrap().dir, Direction::Right);
+        // Right on a sector edge is rejected instead of flipping
+        assert!(matches!(at(22.5), Err(SwipeReject::DeadZone { .. })));
+        assert!(matches!(at(-67.0), Err(SwipeReject::DeadZone { .. })));
+        assert_eq!(c.classify(0.0, 0.0), Err(SwipeReject::NoMotion));
+        // Confidence falls off towards the edge
+        let (near, far) = (at(40.0).unwrap(), at(28.0).unwrap());
+        assert_eq!((near.dir, far.dir), (Direction::UpRight, Direction::UpRight));
+        assert!(near.confidence > far.confidence && far.confidence > 0.0);
+
+        // The detector swallows an ambiguous gesture and keeps the reason
+        let cfg = SwipeConfig::new(320.0);
+        let mut sd = SwipeDetector::new();
+        for _ in 0..6 { sd.update(&cfg, SwipeSample { dt_ms: 10, dx: 40.0, dy: 16.6 }); }
+        assert_eq!(sd.state, SwipeState::Locked);
+        assert!(matches!(sd.last, Some(Err(SwipeReject::DeadZone { .. }))));
+        sd.reset();
+        assert!(sd.last.is_some());
+    }
+
+    #[test]
+    fn two_finger_gestures_map_to_opposed_pairs() {
+        assert_eq!(classify_pair((0.0, 50.0), (0.0, -50.0)), Ok(Direction::UpDown)); // spread
+        assert_eq!(classify_pair((5.0, -40.0), (-3.0, 45.0)), Ok(Direction::UpDown)); // pinch
+        assert_eq!(classify_pair((-60.0, 4.0), (55.0, -6.0)), Ok(Direction::LeftRight));
+        assert_eq!(classify_pair((30.0, 40.0), (35.0, 35.0)), Err(SwipeReject::NotOpposed));
+        assert!(matches!(classify_pair((40.0, 40.0), (-40.0, -40.0)), Err(SwipeReject::OffAxis { .. })));
+
+        // Waits for its own lock time and for both fingers to travel
+        let cfg = SwipeConfig::new(320.0);
+        let mut tf = TwoFingerDetector::default();
+        assert_eq!(tf.update(&cfg, 10, (0.0, 60.0), (0.0, -60.0)), None);
+        assert_eq!(tf.update(&cfg, 10, (0.0, 0.0), (0.0, 0.0)), None);
+        assert_eq!(tf.update(&cfg, 25, (0.0, 1.0), (0.0, -1.0)), Some(Direction::UpDown));
+        assert_eq!(tf.update(&cfg, 10, (0.0, 60.0), (0.0, -60.0)), None); // locked until reset
+        tf.reset();
+        assert_eq!(tf.last, Some(Ok(Direction::UpDown)));
+        assert_eq!(tf.update(&cfg, 50, (80.0, 0.0), (0.0, 0.0)), None); // second finger still
+    }
+
+    #[test]
+    fn touch_gestures_track_each_finger_and_pick_an_owner() {
+        let cfg = SwipeConfig::new(320.0);
+        // A resting thumb (finger 1) doesn't bend finger 2's swipe; with FirstDown only finger 1 may swipe
+        let resting = |owner| {
+            let mut tg = TouchGestures::new(owner, SwipeClassifier::default());
+            tg.down(1);
+            tg.down(2);
+            (0..6).find_map(|_| tg.update(&cfg, 10, |id| if id == 2 { (30.0, 0.0) } else { (0.0, 0.5) }))
+        };
+        assert_eq!(resting(SwipeOwner::FirstDown), None);
+        assert_eq!(resting(SwipeOwner::LargestDisplacement), Some(Direction::Right));
+
+        // Each finger keeps its own vector instead of one merged one
+        let mut tg = TouchGestures::new(SwipeOwner::FirstDown, SwipeClassifier::default());
+        tg.down(7);
+        tg.down(8);
+        let mut fired = Vec::new();
+        for _ in 0..6 {
+            fired.extend(tg.update(&cfg, 10, |id| if id == 7 { (0.0, 30.0) } else { (0.0, -30.0) }));
+        }
+        assert_eq!(fired, vec![Direction::UpDown]);
+        assert_eq!(tg.detector(7).unwrap().lock_dir, Some(Direction::Up));
+        assert_eq!(tg.detector(8).unwrap().lock_dir, Some(Direction::Down));
+        tg.up(7);
+        assert_eq!(tg.update(&cfg, 10, |_| (0.0, -30.0)), None); // one swipe per gesture
+        tg.up(8);
+        tg.down(9);
+        let next = (0..6).find_map(|_| tg.update(&cfg, 10, |_| (-30.0, 0.0)));
+        assert_eq!((next, tg.finger_count()), (Some(Direction::Left), 1));
+    }
+
     #[test]
     fn log_replay_is_deterministic() {
         let mut dm = dm_at(0);
@@ -162,4 +879,94 @@ mod tests {
         let log = dm.last_duel_log().expect("has log");
         replay_round(&log).expect("replay matches");
     }
+
+    #[test]
+    fn gamepad_chords_flicks_and_slots() {
+        assert_eq!(held_direction(true, true, false, false), Some(Direction::UpDown));
+        assert_eq!(held_direction(true, false, false, true), Some(Direction::UpRight));
+        assert_eq!(held_direction(false, false, true, false), Some(Direction::Left));
+        assert_eq!(held_direction(false, false, false, false), None);
+        let mut flick = StickFlick::new(FlickConfig::default(), SwipeClassifier::default());
+        // Fast push up fires once, then waits for the stick to come back to rest
+        assert_eq!(flick.update(16, 0.0, 0.5), None);
+        assert_eq!(flick.update(16, 0.0, 1.0), Some(Direction::Up));
+        assert_eq!(flick.update(16, 0.0, 1.0), None);
+        assert_eq!(flick.update(16, 0.0, 0.0), None);
+        // A slow push is rejected even when it gets there
+        for _ in 0..8 { assert_eq!(flick.update(16, -0.5, 0.0), None); }
+        assert_eq!(flick.update(16, -1.0, 0.0), None);
+        assert!(matches!(flick.last, Some(Err(SwipeReject::TooSlow { .. }))));
+        flick.update(16, 0.0, 0.0);
+        assert_eq!(flick.update(16, -0.7, -0.7), Some(Direction::DownLeft));
+        let mut slots = PadSlots::new(vec![(5, Actor::Ai)]);
+        assert_eq!(slots.connect(5), Actor::Ai);
+        assert_eq!(slots.connect(0), Actor::Human);
+        assert_eq!(slots.connect(1), Actor::Human);
+        slots.disconnect(0);
+        assert_eq!(slots.actor(0), None);
+        assert_eq!(slots.connect(0), Actor::Human);
+        assert_eq!(slots.actor(5), Some(Actor::Ai));
+    }
+
+    #[test]
+    fn key_chords_join_presses_within_the_window() {
+        let mut chord = KeyChord::new(30);
+        // Up, then Right on a later frame: one diagonal stamped at the first press
+        assert_eq!(chord.update(1_000_000, true, [true, false, false, false]), None);
+        assert_eq!(chord.update(1_016_000, true, [true, false, false, true]), Some((Direction::UpRight, 1_000_000)));
+        // Holding on starts nothing new
+        assert_eq!(chord.update(1_032_000, false, [true, false, false, true]), None);
+        // A lone key commits once the window runs out, even if already released
+        assert_eq!(chord.update(2_000_000, true, [false, false, true, false]), None);
+        assert_eq!(chord.update(2_016_000, false, [false, false, false, false]), None);
+        assert_eq!(chord.update(2_032_000, false, [false, false, false, false]), Some((Direction::Left, 2_000_000)));
+        // The machine takes the early stamp as the reaction
+        let mut dm = DuelMachine::new(DuelConfig { seed: 3, ..DuelConfig::default() }, 0);
+        dm.open_input(1_000);
+        let mut chord = KeyChord::new(30);
+        chord.update(1_040_000, true, [false, false, true, false]);
+        let (dir, ts_us) = chord.update(1_070_000, false, [false; 4]).expect("window ran out");
+        dm.on_swipe_us(Actor::Human, dir, ts_us);
+        dm.tick(1_000 + INPUT_WINDOW_MS + 1);
+        assert_eq!(dm.round_results.last().unwrap().human_reaction_ms, Some(40));
+
+        // Up 10 ms before the deadline, Left a frame after it: the round waits for the chord
+        let mut dm = DuelMachine::new(DuelConfig { seed: 3, ..DuelConfig::default() }, 0);
+        let go = 1_000;
+        dm.open_input(go);
+        dm.human_opening = Opening::UpLeft;
+        let mut chord = KeyChord::new(KEY_CHORD_WINDOW_MS);
+        let press = go + INPUT_WINDOW_MS - 10;
+        for (now, held) in [(press, [true, false, false, false]), (press + 16, [true, false, true, false])] {
+            dm.tick(now);
+            if let Some((dir, ts_us)) = chord.update(now * 1000, true, held) { dm.on_swipe(Actor::Human, dir, ts_us / 1000); }
+            dm.hold_for_chord(Actor::Human, chord.start_us.map(|us| us / 1000));
+        }
+        dm.tick(press + 100);
+        let last = dm.round_results.last().unwrap();
+        assert_eq!((last.outcome, last.human_reaction_ms), (Outcome::HumanWin, Some(INPUT_WINDOW_MS as u32 - 10)));
+    }
+
+    #[test]
+    fn input_bindings_merge_over_defaults_and_reject_conflicts() {
+        assert_eq!(InputBindings::default().validate(), Ok(()));
+        let file: InputBindings = serde_json::from_str(r#"{"bindings":[{"action":"Up","keys":["KeyI"]}]}"#).unwrap();
+        let merged = file.over_defaults().unwrap();
+        let up = merged.get(InputAction::Up).unwrap();
+        assert_eq!(up.keys, vec!["KeyI".to_string()]);
+        assert!(up.pad.is_empty());
+        assert_eq!(merged.get(InputAction::Down), InputBindings::default().get(InputAction::Down));
+        assert_eq!(merged.validate(), Ok(()));
+        // Duel and playground keys may overlap; a global toggle may not
+        let mut clash = InputBindings::default();
+        clash.bindings.iter_mut().find(|b| b.action == InputAction::ToggleEdit).unwrap().keys = vec!["KeyD".into()];
+        assert!(clash.validate().unwrap_err().contains("KeyD"));
+        let mut clash = InputBindings::default();
+        clash.bindings.iter_mut().find(|b| b.action == InputAction::P2Up).unwrap().keys = vec!["KeyW".into()];
+        assert!(clash.validate().is_err());
+        // An action the file binds twice is reported, not quietly resolved by the merge
+        let twice: InputBindings =
+            serde_json::from_str(r#"{"bindings":[{"action":"Up","keys":["KeyI"]},{"action":"Up","keys":["KeyK"]}]}"#).unwrap();
+        assert!(twice.over_defaults().unwrap_err().contains("Up is bound twice"));
+    }
 }
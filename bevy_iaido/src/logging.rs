@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
 
+use crate::config::{COMBO_STEP_WINDOW_MS, DRAW_WINDOW_MS, STRUGGLE_MS};
+use crate::scoring::{score_match, MatchScore};
 use crate::types::*;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -12,6 +14,65 @@ pub struct DuelLog {
     pub human: Option<SwipeEvent>,
     pub ai: Option<SwipeEvent>,
     pub outcome: Outcome,
+    // Combo rounds only; absent in single-swipe logs
+    #[serde(default)]
+    pub human_combo: Vec<Opening>,
+    #[serde(default)]
+    pub ai_combo: Vec<Opening>,
+    #[serde(default)]
+    pub human_steps: Vec<SwipeEvent>,
+    #[serde(default)]
+    pub ai_steps: Vec<SwipeEvent>,
+    #[serde(default)]
+    pub combo_timing: ComboTiming,
+    // Sword-lock rounds: the clash, then the struggle from `struggle_start_ms` (older logs: `go`)
+    #[serde(default)]
+    pub struggle: bool,
+    #[serde(default)]
+    pub struggle_taps: Vec<StruggleTap>,
+    #[serde(default)]
+    pub struggle_start_ms: Option<u64>,
+    // Posture in effect during the round (breaks change windows and tie margins)
+    #[serde(default)]
+    pub posture: Option<PostureState>,
+    #[serde(default)]
+    pub posture_penalty: PosturePenalty,
+    // Draw-then-cut rounds: both draws and the cut cue time (none if the round ended first)
+    #[serde(default)]
+    pub draw_cut: bool,
+    #[serde(default)]
+    pub human_draw: Option<SwipeEvent>,
+    #[serde(default)]
+    pub ai_draw: Option<SwipeEvent>,
+    #[serde(default)]
+    pub cut_ts_ms: Option<u64>,
+    // Handicaps in effect, wrong inputs they forgave and forgives left at round start
+    #[serde(default)]
+    pub human_handicap: Handicap,
+    #[serde(default)]
+    pub ai_handicap: Handicap,
+    #[serde(default)]
+    pub human_forgiven: Option<SwipeEvent>,
+    #[serde(default)]
+    pub ai_forgiven: Option<SwipeEvent>,
+    #[serde(default)]
+    pub human_forgives: u8,
+    #[serde(default)]
+    pub ai_forgives: u8,
+    // Training drill pool the openings were drawn from (empty: all openings)
+    #[serde(default)]
+    pub drill: Vec<Opening>,
+    // Round timing; older logs used the fixed INPUT_WINDOW_MS / TIE_WINDOW_MS
+    #[serde(default)]
+    pub input_window_ms: Option<u64>,
+    #[serde(default)]
+    pub tie_window_ms: Option<u64>,
+    // Whether the AI side was measured from the presented GO as well (hot-seat)
+    #[serde(default)]
+    pub cue_for_ai: bool,
+    // Calibrated input latency taken off those sides' reactions (see calibration)
+    #[serde(default)]
+    pub input_offset_us: u64,
 }
 
 impl DuelLog {
@@ -23,9 +84,16 @@ impl DuelLog {
 pub struct MatchLog {
     pub seed: u32,
     pub rounds: Vec<DuelLog>,
+    // Style score for the human side; older logs have none
+    #[serde(default)]
+    pub score: Option<MatchScore>,
 }
 
 impl MatchLog {
+    pub fn new(seed: u32, rounds: Vec<DuelLog>, results: &[RoundResult]) -> Self {
+        Self { seed, rounds, score: Some(score_match(results)) }
+    }
+
     pub fn to_json(&self) -> String { serde_json::to_string(self).unwrap() }
     pub fn from_json(s: &str) -> serde_json::Result<Self> { serde_json::from_str(s) }
 }
@@ -35,16 +103,70 @@ pub enum ReplayError { OutcomeMismatch, OpeningMismatch }
 
 pub fn replay_round(log: &DuelLog) -> Result<(), ReplayError> {
     use crate::state_machine::{DuelConfig, DuelMachine};
-    let mut dm = DuelMachine::new(DuelConfig { seed: log.seed, clash: true }, log.go.ts_ms);
+    let cfg = DuelConfig {
+        seed: log.seed,
+        clash: true,
+        combo_len: log.human_combo.len().max(1) as u8,
+        combo_timing: log.combo_timing,
+        struggle: log.struggle,
+        posture: log.posture.is_some(),
+        posture_penalty: log.posture_penalty,
+        draw_cut: log.draw_cut || log.cut_ts_ms.is_some(),
+        human_handicap: log.human_handicap,
+        ai_handicap: log.ai_handicap,
+        drill: log.drill.clone(),
+        ..DuelConfig::default()
+    };
+    let mut dm = DuelMachine::new(cfg, log.go.ts_ms);
+    if let Some(w) = log.input_window_ms { dm.input_window_ms = w; }
+    if let Some(t) = log.tie_window_ms { dm.tie_window_ms = t; }
+    dm.cue_for_ai = log.cue_for_ai;
+    dm.input_offset_us = log.input_offset_us;
+    dm.human_forgives = log.human_forgives;
+    dm.ai_forgives = log.ai_forgives;
+    if let Some(p) = log.posture { dm.posture = p; }
     // Force opening identity to match
     if dm.human_opening != log.human_opening || dm.ai_opening != log.ai_opening { return Err(ReplayError::OpeningMismatch); }
-    // Force into input window at GO
-    dm.open_input(log.go.ts_ms);
-    // Feed inputs
-    if let Some(h) = &log.human { dm.on_swipe(Actor::Human, h.dir, h.ts_ms); }
-    if let Some(a) = &log.ai { dm.on_swipe(Actor::Ai, a.dir, a.ts_ms); }
-    // Resolve immediately after window
-    dm.tick(log.go.ts_ms + 1000);
+    if dm.human_combo != log.human_combo || dm.ai_combo != log.ai_combo { return Err(ReplayError::OpeningMismatch); }
+    if log.struggle {
+        let start = log.struggle_start_ms.unwrap_or(log.go.ts_ms);
+        dm.open_struggle(start);
+        for t in &log.struggle_taps { dm.on_struggle_input(t.actor, t.ts_ms); }
+        dm.tick(start + STRUGGLE_MS);
+        let last = dm.round_results.last().expect("round result exists");
+        if last.outcome != log.outcome { return Err(ReplayError::OutcomeMismatch); }
+        return Ok(());
+    }
+    // Force into input window at GO (draw-then-cut: draw at GO, then straight to the cut cue)
+    if dm.cfg.draw_cut {
+        dm.open_draw(log.go.ts_ms);
+        if let Some(p) = log.go.presented { dm.present_go(p); }
+        if let Some(h) = &log.human_draw { dm.on_swipe_event(Actor::Human, h.clone()); }
+        if let Some(a) = &log.ai_draw { dm.on_swipe_event(Actor::Ai, a.clone()); }
+        match (log.cut_ts_ms, &log.human_draw, &log.ai_draw) {
+            (Some(cut), _, _) => dm.open_cut(cut),
+            // Both drew but someone cut before the cue: wait in CutDelay for the early swipe
+            (None, Some(h), Some(a)) => dm.tick(h.ts_ms.max(a.ts_ms)),
+            // A missing draw ends the round when the draw window closes
+            (None, _, _) if log.human.is_none() && log.ai.is_none() => dm.tick(log.go.ts_ms + DRAW_WINDOW_MS),
+            (None, _, _) => {}
+        }
+    } else {
+        dm.open_input(log.go.ts_ms);
+        if let Some(p) = log.go.presented { dm.present_go(p); }
+    }
+    // Feed inputs in time order; combo rounds replay every step, forgiven swipes go in between
+    let feed = |single: &Option<SwipeEvent>, steps: &[SwipeEvent], forgiven: &Option<SwipeEvent>| {
+        let mut evs: Vec<SwipeEvent> = if log.human_combo.is_empty() { single.iter().cloned().collect() } else { steps.to_vec() };
+        evs.extend(forgiven.iter().cloned());
+        evs.sort_by_key(|e| e.ts_ms);
+        evs
+    };
+    for h in feed(&log.human, &log.human_steps, &log.human_forgiven) { dm.on_swipe_event(Actor::Human, h); }
+    for a in feed(&log.ai, &log.ai_steps, &log.ai_forgiven) { dm.on_swipe_event(Actor::Ai, a); }
+    // Resolve immediately after window (combo steps extend it)
+    let origin = log.cut_ts_ms.unwrap_or(log.go.ts_ms);
+    dm.tick(origin + 1000 + COMBO_STEP_WINDOW_MS * log.human_combo.len() as u64);
     let last = dm.round_results.last().expect("round result exists");
     if last.outcome != log.outcome { return Err(ReplayError::OutcomeMismatch); }
     Ok(())
@@ -1,9 +1,101 @@
-use bevy_iaido::{load_log, replay_match};
+use bevy_iaido::{load_json, save_json, BracketKind, DeviceProfile, DEVICE_FILE, Entrant, Tournament, Challenge, ChallengeExport, DailyChallenge, Date, load_log, replay_match, IaidoSettings, MatchLog, Opening, SurvivalSchedule, BEST_MATCH_FILE, ROUNDS_TO_WIN, TOURNAMENT_FILE, TRAINING_ROUNDS};
 
 fn main() {
-    // Simple CLI: --replay <path>
+    // Simple CLI: --replay <path> | --train [up,updown,...] | --ghost [path] | --survival | --daily [YYYY-MM-DD]
+    //   | --challenge <code> [rival.json] | --tournament <single|double|rr> <name,ai:tier,...> [first_to] | --tournament resume
+    //   | --calibrate | --calibrate-swipe | --screen-mm <width>
     let mut args = std::env::args().skip(1);
     if let Some(cmd) = args.next() {
+        if cmd == "--train" {
+            let drill: Result<Vec<Opening>, String> = match args.next() {
+                Some(list) => list.split(',').map(str::parse).collect(),
+                None => Ok(Vec::new()),
+            };
+            match drill {
+                Ok(drill) => bevy_iaido::run_game_with(IaidoSettings::training(drill, TRAINING_ROUNDS)),
+                Err(e) => eprintln!("{}", e),
+            }
+            return;
+        }
+        if cmd == "--calibrate" {
+            bevy_iaido::run_game_with(IaidoSettings::calibration());
+            return;
+        }
+        if cmd == "--calibrate-swipe" {
+            bevy_iaido::run_game_with(IaidoSettings::swipe_calibration());
+            return;
+        }
+        if cmd == "--screen-mm" {
+            // Saved with the device calibration; swipes are then sized from the real DPI
+            let mm = args.next().map(|mm| mm.parse::<f32>().ok().filter(|mm| *mm > 0.0).ok_or(mm));
+            match mm {
+                Some(Ok(mm)) => {
+                    let device = DeviceProfile { screen_mm: Some(mm), ..load_json(DEVICE_FILE).unwrap_or_default() };
+                    match save_json(DEVICE_FILE, &device) {
+                        Ok(()) => println!("Screen width {} mm saved", mm),
+                        Err(e) => eprintln!("Failed to save screen width: {}", e),
+                    }
+                }
+                Some(Err(mm)) => eprintln!("bad screen width: {}", mm),
+                None => eprintln!("Usage: --screen-mm <width in mm>"),
+            }
+            return;
+        }
+        if cmd == "--survival" {
+            bevy_iaido::run_game_with(IaidoSettings::survival(SurvivalSchedule::default()));
+            return;
+        }
+        if cmd == "--daily" {
+            // Another date's challenge can be practised; only today's attempt counts
+            let date: Result<Date, String> = args.next().map_or(Ok(Date::today()), |d| d.parse());
+            match date {
+                Ok(date) => bevy_iaido::run_game_with(IaidoSettings::daily(DailyChallenge::for_date(date))),
+                Err(e) => eprintln!("{}", e),
+            }
+            return;
+        }
+        if cmd == "--challenge" {
+            let Some(code) = args.next() else {
+                eprintln!("Usage: --challenge <code> [rival.json]");
+                return;
+            };
+            let challenge = match Challenge::from_code(&code) {
+                Ok(challenge) => challenge,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+            // Optional friend's export to compare against; it must be for the same code
+            let rival = args.next().map(|path| ChallengeExport::load(&path).ok_or(path));
+            match rival {
+                Some(Err(path)) => eprintln!("Failed to load challenge export: {}", path),
+                Some(Ok(r)) if Challenge::from_code(&r.code).ok().as_ref() != Some(&challenge) => {
+                    eprintln!("Export {} is for a different challenge", r.code)
+                }
+                rival => bevy_iaido::run_game_with(IaidoSettings::challenge(&challenge, rival.and_then(Result::ok))),
+            }
+            return;
+        }
+        if cmd == "--tournament" {
+            match tournament_from_args(&mut args) {
+                Ok(bracket) => run_tournament(bracket),
+                Err(e) => eprintln!("{}", e),
+            }
+            return;
+        }
+        if cmd == "--ghost" {
+            // Default to the saved personal best
+            let log = match args.next() {
+                Some(path) => load_log(&path),
+                None => load_json::<MatchLog>(BEST_MATCH_FILE),
+            };
+            match log {
+                Some(log) => bevy_iaido::run_game_with(IaidoSettings::ghost_race(log)),
+                None => eprintln!("No ghost log to race"),
+            }
+            return;
+        }
         if cmd == "--replay" {
             if let Some(path) = args.next() {
                 match load_log(&path) {
@@ -20,3 +112,35 @@ fn main() {
 
     bevy_iaido::run_game();
 }
+
+fn tournament_from_args(args: &mut impl Iterator<Item = String>) -> Result<Tournament, String> {
+    let kind = args.next().ok_or("Usage: --tournament <single|double|rr> <name,ai:tier,...> [first_to] | --tournament resume")?;
+    if kind == "resume" {
+        return load_json::<Tournament>(TOURNAMENT_FILE).ok_or_else(|| "No saved tournament to resume".to_string());
+    }
+    let kind: BracketKind = kind.parse()?;
+    let entrants: Vec<Entrant> = args.next().ok_or("Missing entrant list")?.split(',').map(str::parse).collect::<Result<_, _>>()?;
+    let first_to = match args.next() {
+        Some(n) => n.parse().map_err(|_| format!("bad first_to: {}", n))?,
+        None => ROUNDS_TO_WIN,
+    };
+    let seed = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map_or(0, |d| d.as_secs() as u32);
+    Tournament::new(kind, entrants, seed, first_to)
+}
+
+// All-AI brackets (or ones only AIs are left in) are settled headlessly, no window
+fn run_tournament(mut bracket: Tournament) {
+    bracket.simulate_ai_matches();
+    if let Err(e) = save_json(TOURNAMENT_FILE, &bracket) {
+        eprintln!("Failed to save tournament: {}", e);
+    }
+    match bracket.champion() {
+        Some(champ) => {
+            for (rank, row) in bracket.standings().iter().enumerate() {
+                println!("{:>2}. {:<16} W{} L{} ({:+})", rank + 1, bracket.entrants[row.entrant].name, row.wins, row.losses, row.round_diff);
+            }
+            println!("Champion: {}", bracket.entrants[champ].name);
+        }
+        None => bevy_iaido::run_game_with(IaidoSettings::tournament(bracket)),
+    }
+}
@@ -8,36 +8,224 @@ use bevy::asset::AssetServer;
 #[cfg(feature = "bevy")]
 use bevy_kira_audio::prelude::{AudioSource as KiraAudioSource, *};
 #[cfg(feature = "bevy")]
+use bevy::input::gamepad::{GamepadConnection, GamepadConnectionEvent};
+#[cfg(feature = "bevy")]
 use bevy::input::mouse::MouseMotion;
 
+/// Which front-end experience runs on top of the duel core.
 #[cfg(feature = "bevy")]
-#[derive(Debug, Clone, Copy, Resource)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GameMode {
+    #[default]
+    Duel,
+    /// Solo drills: per-round feedback and a slowest-openings summary at the end.
+    Training,
+    /// One life against an AI that speeds up while the windows tighten.
+    Survival,
+    /// The date-seeded daily challenge; the first attempt each day is scored.
+    Daily,
+    /// A shared challenge code, optionally against a friend's exported run.
+    Challenge,
+    /// Bracket play: human matches on screen, AI-vs-AI matches simulated between them.
+    Tournament,
+    /// Tap along to a metronome to measure this device's input latency; no duel.
+    Calibration,
+    /// A few comfortable swipes set the minimum swipe distance and direction lock; no duel.
+    SwipeCalibration,
+}
+
+#[cfg(feature = "bevy")]
+#[derive(Debug, Clone, Resource)]
 pub struct IaidoSettings {
     pub seed: u32,
-    pub dpi: f32,
+    /// Fixed DPI of the logical pixels input arrives in; `None` detects it from the window.
+    pub dpi: Option<f32>,
+    /// Physical width of the window in mm, when known (e.g. fullscreen on a known phone),
+    /// so DPI is measured rather than assumed from the platform's REFERENCE_DPI.
+    /// `None` falls back to the width saved in `DeviceProfile::screen_mm`.
+    pub screen_mm: Option<f32>,
+    /// Angle sectors and dead zones for touch/mouse swipes.
+    pub swipe: SwipeClassifier,
+    /// Which finger's swipe counts when several are down.
+    pub swipe_owner: SwipeOwner,
+    /// How long key presses are collected into one direction.
+    pub chord_ms: u64,
+    /// Stick flick thresholds for gamepads.
+    pub flick: FlickConfig,
+    /// Gamepads pinned to a side by id; others are assigned as they connect.
+    pub pad_slots: Vec<(usize, Actor)>,
+    /// Drive the AI opponent; off leaves the human alone on the field.
     pub ai: bool,
+    /// Duel rules and mutators; `seed` above overrides `duel.seed`.
+    pub duel: DuelConfig,
+    pub mode: GameMode,
+    /// Race a recorded match instead of the AI profile.
+    pub ghost: Option<MatchLog>,
+    pub ai_tier: AiTier,
+    /// Today's challenge, when `mode` is `Daily`.
+    pub daily: Option<DailyChallenge>,
+    /// Friend's run to beat, when `mode` is `Challenge`.
+    pub rival: Option<ChallengeExport>,
+    /// Bracket to play (new or resumed), when `mode` is `Tournament`.
+    pub tournament: Option<Tournament>,
+    /// How far the GO sound trails the GO frame on this device; reactions count from the sound.
+    pub audio_latency_ms: u32,
 }
 
 #[cfg(feature = "bevy")]
 impl Default for IaidoSettings {
-    fn default() -> Self { Self { seed: 0xA1D0_5EED, dpi: 320.0, ai: true } }
+    fn default() -> Self { Self { seed: 0xA1D0_5EED, dpi: None, screen_mm: None, swipe: SwipeClassifier::default(), swipe_owner: SwipeOwner::FirstDown, chord_ms: KEY_CHORD_WINDOW_MS, flick: FlickConfig::default(), pad_slots: Vec::new(), ai: true, duel: DuelConfig::default(), mode: GameMode::Duel, ghost: None, ai_tier: AiTier::Dumb, daily: None, rival: None, tournament: None, audio_latency_ms: AUDIO_LATENCY_MS } }
+}
+
+#[cfg(feature = "bevy")]
+impl IaidoSettings {
+    /// Solo drill session over `drill` (all openings when empty), `rounds` long.
+    pub fn training(drill: Vec<Opening>, rounds: u16) -> Self {
+        let duel = DuelConfig { clash: false, format: MatchFormat::Rounds(rounds), drill, ..DuelConfig::default() };
+        Self { ai: false, duel, mode: GameMode::Training, ..Self::default() }
+    }
+
+    /// Endless rounds until the first loss, tightening per `schedule`.
+    pub fn survival(schedule: SurvivalSchedule) -> Self {
+        let duel = DuelConfig { format: MatchFormat::Survival, survival: schedule, ..DuelConfig::default() };
+        Self { duel, mode: GameMode::Survival, ..Self::default() }
+    }
+
+    /// Race the human side of `log`; its seed brings back the same openings.
+    pub fn ghost_race(log: MatchLog) -> Self {
+        Self { seed: log.seed, ghost: Some(log), ..Self::default() }
+    }
+
+    /// Play `challenge` with its seed, ruleset and AI tier.
+    pub fn daily(challenge: DailyChallenge) -> Self {
+        Self {
+            seed: challenge.seed,
+            duel: challenge.duel_config(),
+            ai_tier: challenge.ai,
+            mode: GameMode::Daily,
+            daily: Some(challenge),
+            ..Self::default()
+        }
+    }
+
+    /// Play a decoded challenge code, with a friend's export to compare against.
+    pub fn challenge(challenge: &Challenge, rival: Option<ChallengeExport>) -> Self {
+        Self {
+            seed: challenge.seed,
+            duel: challenge.duel_config(),
+            ai_tier: challenge.ai,
+            mode: GameMode::Challenge,
+            rival,
+            ..Self::default()
+        }
+    }
+
+    /// Run `bracket` to the end; progress is saved to `TOURNAMENT_FILE` after each match.
+    pub fn tournament(bracket: Tournament) -> Self {
+        Self { seed: bracket.seed, mode: GameMode::Tournament, tournament: Some(bracket), ..Self::default() }
+    }
+
+    /// Latency calibration; the estimate is saved to `DEVICE_FILE` and corrects later matches.
+    pub fn calibration() -> Self {
+        Self { ai: false, mode: GameMode::Calibration, ..Self::default() }
+    }
+
+    /// Swipe calibration; saved to `DEVICE_FILE` next to the latency estimate.
+    pub fn swipe_calibration() -> Self {
+        Self { ai: false, mode: GameMode::SwipeCalibration, ..Self::default() }
+    }
 }
 
 #[cfg(feature = "bevy")]
 #[derive(Resource)]
 pub struct DuelRuntime {
     pub machine: DuelMachine,
+    /// Mouse-drag swipes; touch has one detector per finger in `touch`.
     pub swipe: SwipeDetector,
+    pub touch: TouchGestures,
+    /// Key chords for the human side and, in hot-seat, player two.
+    pub chords: [KeyChord; 2],
     cfg: SwipeConfig,
     ai_rng: XorShift32,
     ai_plan: Option<AiPlan>,
     ai_profile: AiProfile,
+    ai_next_tap_ms: Option<u64>,
+    /// Recorded run standing in for the AI profile, if racing a ghost.
+    pub ghost: Option<Ghost>,
+    // Match log written once per finished match
+    match_saved: bool,
+    /// Code and log of the last finished match (see `CHALLENGE_FILE`).
+    pub export: Option<ChallengeExport>,
+    /// Two humans on one keyboard: arrows drive the AI side instead of the AI.
+    pub hot_seat: bool,
+}
+
+/// The bracket being played and which of its matches is on screen.
+#[cfg(feature = "bevy")]
+#[derive(Resource)]
+pub struct TournamentRun {
+    pub bracket: Tournament,
+    pub current: Option<usize>,
+    /// The on-screen human side is entrant b of the current match.
+    pub swapped: bool,
+    // Current match result already written to the bracket
+    reported: bool,
+}
+
+/// Latency calibration in progress (`GameMode::Calibration`).
+#[cfg(feature = "bevy")]
+#[derive(Resource)]
+pub struct CalibrationRun {
+    pub cal: LatencyCalibration,
+    next_beat_ms: u64,
+    // A beat fired last frame and is on screen from this one
+    beat_pending: bool,
+    /// Saved estimate, once the run is over (None if too few taps counted).
+    pub result: Option<LatencyEstimate>,
+    pub finished: bool,
+}
+
+/// Swipe calibration in progress (`GameMode::SwipeCalibration`).
+#[cfg(feature = "bevy")]
+#[derive(Resource)]
+pub struct SwipeCalibrationRun {
+    pub cal: SwipeCalibration,
+    // Only the first finger down is measured
+    finger: Option<(u64, Vec2)>,
+    pub result: Option<SwipeEstimate>,
+    pub finished: bool,
+}
+
+/// Records loaded from disk at startup and updated when a run beats them.
+#[cfg(feature = "bevy")]
+#[derive(Resource, Default)]
+pub struct Records {
+    pub bests: PersonalBests,
+    /// Set when the match just finished beat a record.
+    pub new_best: bool,
+    pub daily: DailyHistory,
+    /// The running daily attempt is the scored one (first of the day).
+    pub daily_scored: bool,
+    pub profile: PlayerProfile,
+    /// Rating change from the match just finished, if it was rated.
+    pub rating_change: Option<RatingEntry>,
+    pub device: DeviceProfile,
 }
 
 #[cfg(feature = "bevy")]
 #[derive(Event)]
 pub struct GoCue;
 
+/// Metronome beat of the latency calibration.
+#[cfg(feature = "bevy")]
+#[derive(Event)]
+pub struct BeatCue;
+
+/// Second cue of a draw-then-cut round: the cut may now land.
+#[cfg(feature = "bevy")]
+#[derive(Event)]
+pub struct CutCue;
+
 #[cfg(feature = "bevy")]
 #[derive(Event)]
 pub struct SlashCue { pub actor: Actor }
@@ -50,6 +238,10 @@ pub struct AttackCue { pub actor: Actor }
 #[derive(Event)]
 pub struct ClashCue;
 
+#[cfg(feature = "bevy")]
+#[derive(Event)]
+pub struct DecoyCue { pub kind: DecoyKind }
+
 use crate::types::Direction as GameDirection;
 
 #[cfg(feature = "bevy")]
@@ -85,15 +277,35 @@ pub struct IaidoPlugin;
 #[cfg(feature = "bevy")]
 impl Plugin for IaidoPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<IaidoSettings>()
+        app.init_resource::<IaidoSettings>();
+        let records = load_records(app.world().resource::<IaidoSettings>());
+        if let Some(bracket) = app.world().resource::<IaidoSettings>().tournament.clone() {
+            app.insert_resource(TournamentRun { bracket, current: None, swapped: false, reported: false });
+        }
+        let pad_slots = app.world().resource::<IaidoSettings>().pad_slots.clone();
+        let mode = app.world().resource::<IaidoSettings>().mode;
+        let calibrating = matches!(mode, GameMode::Calibration | GameMode::SwipeCalibration);
+        if mode == GameMode::Calibration {
+            app.insert_resource(CalibrationRun { cal: LatencyCalibration::new(CALIBRATION_BEAT_MS), next_beat_ms: 0, beat_pending: false, result: None, finished: false });
+        }
+        if mode == GameMode::SwipeCalibration {
+            app.insert_resource(SwipeCalibrationRun { cal: SwipeCalibration::new(REFERENCE_DPI), finger: None, result: None, finished: false });
+        }
+        app
             .init_resource::<DebugState>()
             .init_resource::<AnimationEditMode>()
             .insert_resource(ClearColor(Color::srgba(0.0, 0.0, 0.0, 0.0)))
             .init_resource::<TouchTracker>()
+            .insert_resource(InputMap::load())
+            .insert_resource(GamepadInput { slots: PadSlots::new(pad_slots), flicks: Vec::new() })
+            .insert_resource(records)
             .add_event::<GoCue>()
+            .add_event::<CutCue>()
+            .add_event::<BeatCue>()
             .add_event::<SlashCue>()
             .add_event::<AttackCue>()
             .add_event::<ClashCue>()
+            .add_event::<DecoyCue>()
             .add_event::<InputDetected>()
             .add_event::<DebugInputCue>()
             .add_plugins(bevy_kira_audio::AudioPlugin)
@@ -101,25 +313,32 @@ impl Plugin for IaidoPlugin {
             .add_plugins(visuals::VisualsPlugin)
             .add_plugins(touch::TouchControlsPlugin)
             .add_systems(Startup, (setup, setup_audio))
+            .add_systems(First, stamp_go_presentation.after(bevy::time::TimeSystem))
             .add_systems(Update, (
                 update_time,
                 read_input,
+                read_gamepads,
                 drive_ai,
                 advance_duel,
                 react_outcomes,
                 react_audio,
+                save_match_log,
+                run_tournament,
                 toggle_debug_state,
-            ));
+            ).run_if(move || !calibrating))
+            .add_systems(Update, run_calibration.run_if(resource_exists::<CalibrationRun>))
+            .add_systems(Update, run_swipe_calibration.run_if(resource_exists::<SwipeCalibrationRun>))
+            .add_systems(PreUpdate, update_dpi);
     }
 }
 
 #[cfg(feature = "bevy")]
 fn toggle_debug_state(
-    keys: Res<ButtonInput<KeyCode>>,
+    input: ActionInput,
     mut state: ResMut<DebugState>,
     mut edit_mode: ResMut<AnimationEditMode>,
 ) {
-    if keys.just_pressed(KeyCode::KeyD) {
+    if input.just_pressed(InputAction::ToggleEdit) {
         edit_mode.0 = !edit_mode.0;
         if *state != DebugState::Animation {
             *state = DebugState::Animation;
@@ -129,14 +348,37 @@ fn toggle_debug_state(
 }
 
 #[cfg(feature = "bevy")]
-fn setup(mut commands: Commands, settings: Res<IaidoSettings>) {
+fn load_records(settings: &IaidoSettings) -> Records {
+    let daily: DailyHistory = load_json(DAILY_FILE).unwrap_or_default();
+    // Past dates can be replayed, but only today's first attempt is scored
+    let daily_scored = settings.daily.as_ref().is_some_and(|c| c.date == Date::today() && !daily.played(c.date));
+    Records {
+        bests: load_json(BESTS_FILE).unwrap_or_default(),
+        new_best: false,
+        daily,
+        daily_scored,
+        profile: load_json(PROFILE_FILE).unwrap_or_default(),
+        rating_change: None,
+        device: load_json(DEVICE_FILE).unwrap_or_default(),
+    }
+}
+
+#[cfg(feature = "bevy")]
+fn setup(mut commands: Commands, settings: Res<IaidoSettings>, records: Res<Records>) {
     let now_ms = 0;
-    let machine = DuelMachine::new(DuelConfig { seed: settings.seed, clash: true }, now_ms);
-    let swipe = SwipeDetector::new();
-    let cfg = SwipeConfig { dpi: settings.dpi };
+    let mut machine = DuelMachine::new(DuelConfig { seed: settings.seed, ..settings.duel.clone() }, now_ms);
+    machine.input_offset_us = records.device.input_offset_us();
+    let swipe = SwipeDetector::with_classifier(settings.swipe);
+    let touch = TouchGestures::new(settings.swipe_owner, settings.swipe);
+    let chords = [KeyChord::new(settings.chord_ms); 2];
+    // Refined by update_dpi once the window reports its scale factor
+    let cfg = records.device.swipe_config(settings.dpi.unwrap_or(REFERENCE_DPI));
     let ai_rng = XorShift32::new(settings.seed ^ 0xDEADBEEF);
-    let ai_profile = DUMB;
-    commands.insert_resource(DuelRuntime { machine, swipe, cfg, ai_rng, ai_plan: None, ai_profile });
+    let ai_profile = settings.ai_tier.profile();
+    let ghost = settings.ghost.clone().map(Ghost::new);
+    commands.insert_resource(DuelRuntime {
+        machine, swipe, touch, chords, cfg, ai_rng, ai_plan: None, ai_profile, ai_next_tap_ms: None, ghost, match_saved: false, export: None, hot_seat: false,
+    });
 }
 
 // Audio setup and reactions
@@ -148,6 +390,8 @@ struct AudioHandles {
     draw: Option<Handle<KiraAudioSource>>,
     hit: Option<Handle<KiraAudioSource>>,
     clash: Option<Handle<KiraAudioSource>>,
+    decoy: Option<Handle<KiraAudioSource>>,
+    cut: Option<Handle<KiraAudioSource>>,
 }
 
 #[cfg(feature = "bevy")]
@@ -166,7 +410,9 @@ fn setup_audio(mut commands: Commands, assets: Res<AssetServer>, audio: Res<Audi
     let draw = load_if_exists("audio/draw.ogg");
     let hit = load_if_exists("audio/hit.ogg");
     let clash = load_if_exists("audio/clash.ogg");
-    commands.insert_resource(AudioHandles { _wind: wind.clone(), go, draw, hit, clash });
+    let decoy = load_if_exists("audio/decoy.ogg");
+    let cut = load_if_exists("audio/cut.ogg");
+    commands.insert_resource(AudioHandles { _wind: wind.clone(), go, draw, hit, clash, decoy, cut });
     // Start wind loop quietly if available
     if let Some(wind) = wind {
         audio.play(wind).with_volume(0.2).looped();
@@ -179,11 +425,132 @@ fn update_time(mut rt: ResMut<DuelRuntime>, time: Res<Time>) {
     rt.machine.tick(now_ms);
 }
 
-// Track previous touch positions to calculate delta
+// GO fires during a frame's update and shows when that frame is presented, which is
+// about when the next frame starts: stamp it then
+#[cfg(feature = "bevy")]
+fn stamp_go_presentation(mut rt: ResMut<DuelRuntime>, settings: Res<IaidoSettings>, time: Res<Time>) {
+    if rt.machine.go_ts_ms.is_none() || rt.machine.go_presented.is_some() { return; }
+    let presented_us = time.elapsed().as_micros() as u64;
+    rt.machine.present_go(GoPresentation { presented_us, audio_latency_ms: settings.audio_latency_ms });
+}
+
+// Per-finger touch positions, for each finger's motion since the last frame
 #[cfg(feature = "bevy")]
 #[derive(Resource, Default)]
 struct TouchTracker {
     last_pos: std::collections::HashMap<u64, Vec2>,
+    frame_delta: std::collections::HashMap<u64, Vec2>,
+}
+
+#[cfg(feature = "bevy")]
+impl TouchTracker {
+    // Keeps positions and the gesture's finger list in step; true for a fresh touch
+    fn track(&mut self, ev: &TouchInput, gestures: &mut TouchGestures) -> bool {
+        match ev.phase {
+            bevy::input::touch::TouchPhase::Started => {
+                self.last_pos.insert(ev.id, ev.position);
+                gestures.down(ev.id);
+                true
+            }
+            bevy::input::touch::TouchPhase::Moved => {
+                if let Some(last) = self.last_pos.insert(ev.id, ev.position) {
+                    *self.frame_delta.entry(ev.id).or_default() += ev.position - last;
+                }
+                false
+            }
+            bevy::input::touch::TouchPhase::Ended | bevy::input::touch::TouchPhase::Canceled => {
+                self.last_pos.remove(&ev.id);
+                gestures.up(ev.id);
+                false
+            }
+        }
+    }
+
+    fn delta(&self, id: u64) -> (f32, f32) {
+        self.frame_delta.get(&id).map_or((0.0, 0.0), |d| (d.x, d.y))
+    }
+}
+
+// Up, down, left, right
+#[cfg(feature = "bevy")]
+const P1_ACTIONS: [InputAction; 4] = [InputAction::Up, InputAction::Down, InputAction::Left, InputAction::Right];
+#[cfg(feature = "bevy")]
+const P2_ACTIONS: [InputAction; 4] = [InputAction::P2Up, InputAction::P2Down, InputAction::P2Left, InputAction::P2Right];
+
+#[cfg(feature = "bevy")]
+fn any_key_just(input: &ActionInput, sets: &[[InputAction; 4]]) -> bool {
+    sets.iter().flatten().any(|a| input.just_pressed(*a))
+}
+
+// Direction for a fresh press on any of `sets`; held combinations (checked first) give diagonals
+#[cfg(feature = "bevy")]
+fn key_direction(chord: &mut KeyChord, input: &ActionInput, sets: &[[InputAction; 4]], now_us: u64) -> Option<(GameDirection, u64)> {
+    let held = |i: usize| sets.iter().any(|set| input.pressed(set[i]));
+    chord.update(now_us, any_key_just(input, sets), [held(0), held(1), held(2), held(3)])
+}
+
+// Connected pads, their player slots and left-stick flick detectors
+#[cfg(feature = "bevy")]
+#[derive(Resource)]
+struct GamepadInput {
+    slots: PadSlots,
+    flicks: Vec<(usize, StickFlick)>,
+}
+
+#[cfg(feature = "bevy")]
+#[allow(clippy::too_many_arguments)]
+fn read_gamepads(
+    mut rt: ResMut<DuelRuntime>,
+    mut pads: ResMut<GamepadInput>,
+    settings: Res<IaidoSettings>,
+    map: Res<InputMap>,
+    mut connections: EventReader<GamepadConnectionEvent>,
+    buttons: Res<ButtonInput<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+    mut input_tx: EventWriter<InputDetected>,
+    time: Res<Time>,
+) {
+    let pads = &mut *pads;
+    for ev in connections.read() {
+        let id = ev.gamepad.id;
+        match ev.connection {
+            GamepadConnection::Connected(_) => {
+                pads.slots.connect(id);
+                if pads.flicks.iter().all(|(p, _)| *p != id) {
+                    pads.flicks.push((id, StickFlick::new(settings.flick, settings.swipe)));
+                }
+            }
+            GamepadConnection::Disconnected => {
+                pads.slots.disconnect(id);
+                pads.flicks.retain(|(p, _)| *p != id);
+            }
+        }
+    }
+    let now_ms = (time.elapsed_seconds_f64() * 1000.0) as u64;
+    let now_us = time.elapsed().as_micros() as u64;
+    let dt_ms = (time.delta_seconds_f64() * 1000.0) as u64;
+    for (id, flick) in pads.flicks.iter_mut() {
+        let Some(slot) = pads.slots.actor(*id) else { continue; };
+        // The AI side only takes a pad when a second human plays it
+        let actor = if rt.hot_seat { slot } else { Actor::Human };
+        let gamepad = Gamepad::new(*id);
+        let button = |t: GamepadButtonType| GamepadButton::new(gamepad, t);
+        // Every pad answers to the human side's buttons (d-pad and Y/A/X/B by default)
+        let held = |a: InputAction| map.pad_buttons(a).any(|t| buttons.pressed(button(t)));
+        let fresh = P1_ACTIONS.iter().any(|a| map.pad_buttons(*a).any(|t| buttons.just_pressed(button(t))));
+        let stick = |t: GamepadAxisType| axes.get(GamepadAxis::new(gamepad, t)).unwrap_or(0.0);
+        let flicked = flick.update(dt_ms, stick(GamepadAxisType::LeftStickX), stick(GamepadAxisType::LeftStickY));
+        if rt.machine.phase == DuelPhase::Struggle {
+            if fresh { rt.machine.on_struggle_input(actor, now_ms); }
+            continue;
+        }
+        let [up, down, left, right] = P1_ACTIONS.map(held);
+        let pressed = if fresh { held_direction(up, down, left, right) } else { None };
+        if let Some(dir) = pressed.or(flicked) {
+            rt.machine.on_swipe_us(actor, dir, now_us);
+            input_tx.send(InputDetected { actor, dir });
+        }
+    }
 }
 
 #[cfg(feature = "bevy")]
@@ -193,79 +560,100 @@ fn read_input(
     mut tracker: ResMut<TouchTracker>,
     mouse_buttons: Res<ButtonInput<MouseButton>>,
     mut mouse_motion: EventReader<MouseMotion>,
-    keys: Res<ButtonInput<KeyCode>>,
+    input: ActionInput,
     mut input_tx: EventWriter<InputDetected>,
     time: Res<Time>,
 ) {
     let now_ms = (time.elapsed_seconds_f64() * 1000.0) as u64;
+    // winit hands over events without timestamps: keys carry the frame time, a frame's touch
+    // moves and mouse motion are spread across it and swipes are timed at their threshold crossing
+    let now_us = time.elapsed().as_micros() as u64;
+    let frame_start_us = now_us.saturating_sub(time.delta().as_micros() as u64);
+    let spread = |i: usize, n: usize| frame_start_us + (now_us - frame_start_us) * (i as u64 + 1) / n as u64;
+
+    // Keys: both sets play the human side (WASD and arrows by default), except hot-seat where the second is player two
+    let (p1, p2): (&[[InputAction; 4]], &[[InputAction; 4]]) =
+        if rt.hot_seat { (&[P1_ACTIONS], &[P2_ACTIONS]) } else { (&[P1_ACTIONS, P2_ACTIONS], &[]) };
+    let any_just = any_key_just(&input, p1);
+
+    // Sword-lock: every fresh press, click or touch pushes the meter
+    if rt.machine.phase == DuelPhase::Struggle {
+        let mut tapped = any_just || mouse_buttons.just_pressed(MouseButton::Left);
+        for ev in touches.read() {
+            tapped |= tracker.track(ev, &mut rt.touch);
+        }
+        tracker.frame_delta.clear();
+        mouse_motion.clear();
+        if tapped {
+            rt.machine.on_struggle_input(Actor::Human, now_ms);
+        }
+        if any_key_just(&input, p2) {
+            rt.machine.on_struggle_input(Actor::Ai, now_ms);
+        }
+        rt.swipe.reset();
+        rt.touch.clear();
+        for chord in &mut rt.chords { chord.reset(); }
+        // Fingers still down rejoin as fresh touches after the lock
+        let held: Vec<u64> = tracker.last_pos.keys().copied().collect();
+        for id in held { rt.touch.down(id); }
+        return;
+    }
 
-    // Keys: Check combinations first
-    let up = keys.pressed(KeyCode::ArrowUp) || keys.pressed(KeyCode::KeyW);
-    let down = keys.pressed(KeyCode::ArrowDown) || keys.pressed(KeyCode::KeyS);
-    let left = keys.pressed(KeyCode::ArrowLeft) || keys.pressed(KeyCode::KeyA);
-    let right = keys.pressed(KeyCode::ArrowRight) || keys.pressed(KeyCode::KeyD);
-
-    let any_just = keys.just_pressed(KeyCode::ArrowUp) || keys.just_pressed(KeyCode::KeyW) ||
-                   keys.just_pressed(KeyCode::ArrowDown) || keys.just_pressed(KeyCode::KeyS) ||
-                   keys.just_pressed(KeyCode::ArrowLeft) || keys.just_pressed(KeyCode::KeyA) ||
-                   keys.just_pressed(KeyCode::ArrowRight) || keys.just_pressed(KeyCode::KeyD);
-
-    let mut key_dir = None;
-    if any_just {
-        if up && down { key_dir = Some(GameDirection::UpDown); }
-        else if left && right { key_dir = Some(GameDirection::LeftRight); }
-        else if up && right { key_dir = Some(GameDirection::UpRight); }
-        else if up && left { key_dir = Some(GameDirection::UpLeft); }
-        else if down && right { key_dir = Some(GameDirection::DownRight); }
-        else if down && left { key_dir = Some(GameDirection::DownLeft); }
-        else if up { key_dir = Some(GameDirection::Up); }
-        else if down { key_dir = Some(GameDirection::Down); }
-        else if left { key_dir = Some(GameDirection::Left); }
-        else if right { key_dir = Some(GameDirection::Right); }
-    }
-
-    if let Some(dir) = key_dir {
-        rt.machine.on_swipe(Actor::Human, dir, now_ms);
+    // Chords commit a little late but carry the first press's time
+    let rt = &mut *rt;
+    if let Some((dir, ts_us)) = key_direction(&mut rt.chords[0], &input, p1, now_us) {
+        rt.machine.on_swipe_us(Actor::Human, dir, ts_us);
         input_tx.send(InputDetected { actor: Actor::Human, dir });
     }
+    if let Some((dir, ts_us)) = key_direction(&mut rt.chords[1], &input, p2, now_us) {
+        rt.machine.on_swipe_us(Actor::Ai, dir, ts_us);
+        input_tx.send(InputDetected { actor: Actor::Ai, dir });
+    }
+    rt.machine.hold_for_chord(Actor::Human, rt.chords[0].start_us.map(|us| us / 1000));
+    rt.machine.hold_for_chord(Actor::Ai, rt.chords[1].start_us.map(|us| us / 1000));
 
-    let dt_ms = (time.delta_seconds_f64() * 1000.0) as u64;
-    for ev in touches.read() {
-        match ev.phase {
-            bevy::input::touch::TouchPhase::Started => {
-                tracker.last_pos.insert(ev.id, ev.position);
-            }
-            bevy::input::touch::TouchPhase::Moved => {
-                if let Some(last) = tracker.last_pos.get(&ev.id) {
-                    let dx = ev.position.x - last.x;
-                    let dy = ev.position.y - last.y;
-                    let sample = SwipeSample { dt_ms, dx, dy };
-                    let cfg = rt.cfg.clone();
-                    if let Some(dir) = rt.swipe.update(&cfg, sample) {
-                        rt.machine.on_swipe(Actor::Human, dir, now_ms);
-                        input_tx.send(InputDetected { actor: Actor::Human, dir });
-                    }
-                }
-                tracker.last_pos.insert(ev.id, ev.position);
-            }
-            bevy::input::touch::TouchPhase::Ended | bevy::input::touch::TouchPhase::Canceled => {
-                tracker.last_pos.remove(&ev.id);
-            }
-        }
+    // Every finger has its own detector; the owner policy and two-finger gestures pick one direction.
+    // Each move is fed on its own, so the crossing falls between that move and the one before
+    let events: Vec<TouchInput> = touches.read().cloned().collect();
+    let moves = events.iter().filter(|ev| ev.phase == bevy::input::touch::TouchPhase::Moved).count();
+    let cfg = rt.cfg;
+    let (mut touch_dir, mut fed_us, mut moved) = (None, frame_start_us, 0);
+    for ev in &events {
+        tracker.track(ev, &mut rt.touch);
+        if ev.phase != bevy::input::touch::TouchPhase::Moved { continue; }
+        let ts_us = spread(moved, moves);
+        moved += 1;
+        let hit = rt.touch.update_at(&cfg, ts_us / 1000 - fed_us / 1000, ts_us, |id| tracker.delta(id));
+        touch_dir = touch_dir.or(hit);
+        tracker.frame_delta.clear();
+        fed_us = ts_us;
+    }
+    if moves == 0 {
+        // Resting fingers still age their gestures
+        let dt_ms = (time.delta_seconds_f64() * 1000.0) as u64;
+        touch_dir = rt.touch.update_at(&cfg, dt_ms, now_us, |_| (0.0, 0.0));
+    }
+    if let Some((dir, ts_us)) = touch_dir {
+        rt.machine.on_swipe_us(Actor::Human, dir, ts_us);
+        input_tx.send(InputDetected { actor: Actor::Human, dir });
     }
 
     // Desktop mouse-drag adapter: hold left button and move to generate swipe deltas
     if mouse_buttons.pressed(MouseButton::Left) {
-        for m in mouse_motion.read() {
-            let sample = SwipeSample { dt_ms, dx: m.delta.x, dy: m.delta.y };
+        let motion: Vec<_> = mouse_motion.read().collect();
+        let mut fed_us = frame_start_us;
+        for (i, m) in motion.iter().enumerate() {
+            let ts_us = spread(i, motion.len());
+            let sample = SwipeSample { dt_ms: ts_us / 1000 - fed_us / 1000, dx: m.delta.x, dy: m.delta.y };
+            fed_us = ts_us;
             let cfg = rt.cfg.clone();
-            if let Some(dir) = rt.swipe.update(&cfg, sample) {
-                rt.machine.on_swipe(Actor::Human, dir, now_ms);
+            if let Some((dir, ts_us)) = rt.swipe.update_at(&cfg, sample, ts_us) {
+                rt.machine.on_swipe_us(Actor::Human, dir, ts_us);
                 input_tx.send(InputDetected { actor: Actor::Human, dir });
             }
         }
     } else {
-        // Reset detector when button released to avoid stale state
+        // Reset detector once nothing is held to avoid stale state
         rt.swipe.reset();
     }
 }
@@ -274,22 +662,59 @@ fn read_input(
 fn drive_ai(
     mut rt: ResMut<DuelRuntime>,
     mut input_tx: EventWriter<InputDetected>,
+    settings: Res<IaidoSettings>,
     time: Res<Time>,
 ) {
+    if !settings.ai || rt.hot_seat { return; }
+    let now_ms = (time.elapsed_seconds_f64() * 1000.0) as u64;
+    if rt.machine.phase == DuelPhase::Struggle {
+        let profile = rt.ai_profile;
+        let next = match rt.ai_next_tap_ms {
+            Some(t) => t,
+            None => now_ms + plan_struggle_tap(profile, &mut rt.ai_rng),
+        };
+        if now_ms >= next {
+            rt.machine.on_struggle_input(Actor::Ai, now_ms);
+            rt.ai_next_tap_ms = Some(now_ms + plan_struggle_tap(profile, &mut rt.ai_rng));
+        } else {
+            rt.ai_next_tap_ms = Some(next);
+        }
+        return;
+    }
+    rt.ai_next_tap_ms = None;
+
     // If AI has already swiped for this round, do nothing
     if rt.machine.ai_swipe.is_some() {
         return;
     }
 
-    let now_ms = (time.elapsed_seconds_f64() * 1000.0) as u64;
-    // Plan AI on GO
+    // Drawn and waiting for the cut cue: hold still or it's a false start
+    if rt.machine.awaiting_cut(Actor::Ai) {
+        rt.ai_plan = None;
+        return;
+    }
+
+    // Plan AI on GO (or the cut cue), then once per combo step counted from the previous step
     if let Some(go) = rt.machine.go_ts_ms {
+        let go = rt.machine.cut_ts_ms.unwrap_or(go);
+        let last_step = rt.machine.steps(Actor::Ai).last().map(|e| e.ts_ms);
         if rt.ai_plan.is_none() {
-            rt.ai_plan = Some(plan_for_go(rt.ai_profile, &mut rt.ai_rng));
+            let round = rt.machine.round_results.len();
+            let step = rt.machine.steps(Actor::Ai).len();
+            let drawing = rt.machine.phase == DuelPhase::DrawWindow;
+            // Survival speeds the AI up with every round survived
+            let profile = survival_profile(rt.ai_profile, &rt.machine.cfg.survival, rt.machine.survival_round());
+            // A ghost with nothing recorded for this moment just stands there
+            let ghost_plan = rt.ghost.as_ref().map(|g| if drawing { g.plan_draw(round) } else { g.plan(round, step) });
+            rt.ai_plan = match ghost_plan {
+                Some(plan) => plan,
+                None if last_step.is_some() => Some(plan_for_step(profile, &mut rt.ai_rng)),
+                None => Some(plan_for_go(profile, &mut rt.ai_rng)),
+            };
         }
         if let Some(plan) = rt.ai_plan.clone() {
-            if now_ms >= go + plan.reaction_ms {
-                let dir = plan.decide_dir(rt.machine.ai_opening, rt.ai_rng);
+            if now_ms >= last_step.unwrap_or(go) + plan.reaction_ms {
+                let dir = plan.decide_dir(rt.machine.next_opening(Actor::Ai), rt.ai_rng);
                 rt.machine.on_swipe(Actor::Ai, dir, now_ms);
                 input_tx.send(InputDetected { actor: Actor::Ai, dir });
                 rt.ai_plan = None;
@@ -301,12 +726,162 @@ fn drive_ai(
 }
 
 #[cfg(feature = "bevy")]
-fn advance_duel(mut rt: ResMut<DuelRuntime>, mut go_tx: EventWriter<GoCue>, time: Res<Time>) {
+fn advance_duel(
+    mut rt: ResMut<DuelRuntime>,
+    mut go_tx: EventWriter<GoCue>,
+    mut cut_tx: EventWriter<CutCue>,
+    mut decoy_tx: EventWriter<DecoyCue>,
+    time: Res<Time>,
+) {
+    if let Some(decoy) = rt.machine.take_fired_decoy() {
+        decoy_tx.send(DecoyCue { kind: decoy.kind });
+    }
     if matches!(rt.machine.phase, DuelPhase::GoSignal) {
         go_tx.send(GoCue);
         let now_ms = (time.elapsed_seconds_f64() * 1000.0) as u64;
         rt.machine.tick(now_ms); // advance into input window immediately
     }
+    if matches!(rt.machine.phase, DuelPhase::CutSignal) {
+        cut_tx.send(CutCue);
+        let now_ms = (time.elapsed_seconds_f64() * 1000.0) as u64;
+        rt.machine.tick(now_ms);
+    }
+}
+
+// Only straight matches against a rated AI move the rating (no ghosts, drills or hot-seat)
+#[cfg(feature = "bevy")]
+fn is_rated(settings: &IaidoSettings, hot_seat: bool, format: MatchFormat) -> bool {
+    settings.ai && settings.ghost.is_none() && !hot_seat && matches!(format, MatchFormat::FirstTo(_))
+}
+
+// Keep the last match, and the best-scoring one for ghost races
+#[cfg(feature = "bevy")]
+fn save_match_log(mut rt: ResMut<DuelRuntime>, mut records: ResMut<Records>, settings: Res<IaidoSettings>) {
+    if rt.machine.match_state == MatchState::InProgress {
+        rt.match_saved = false;
+        records.new_best = false;
+        records.rating_change = None;
+        // Claim the day as soon as the scored attempt starts, so quitting mid-match is a loss
+        if let (true, Some(challenge)) = (records.daily_scored, settings.daily.as_ref()) {
+            if records.daily.begin(challenge.date, challenge.code()) {
+                if let Err(e) = save_json(DAILY_FILE, &records.daily) {
+                    println!("Failed to save daily attempt: {}", e);
+                }
+            }
+        }
+        return;
+    }
+    if rt.match_saved { return; }
+    rt.match_saved = true;
+    let won = match rt.machine.match_state { MatchState::HumanWon => Some(true), MatchState::AiWon => Some(false), _ => None };
+    let rated = is_rated(&settings, rt.hot_seat, rt.machine.cfg.format);
+    if let Some(won) = won.filter(|_| rated) {
+        let ts_s = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        let entry = records.profile.record(rt.ai_profile.rating, won, ts_s);
+        records.rating_change = Some(entry);
+        if let Err(e) = save_json(PROFILE_FILE, &records.profile) {
+            println!("Failed to save profile: {}", e);
+        }
+    }
+    if rt.machine.cfg.format == MatchFormat::Survival {
+        let streak = rt.machine.survival_round();
+        if streak > records.bests.survival_streak {
+            records.bests.survival_streak = streak;
+            records.new_best = true;
+            if let Err(e) = save_json(BESTS_FILE, &records.bests) {
+                println!("Failed to save personal bests: {}", e);
+            }
+        }
+    }
+    let log = rt.machine.match_log();
+    let export = ChallengeExport { code: Challenge::from_config(&rt.machine.cfg, settings.ai_tier).code(), log: log.clone() };
+    println!("Challenge code: {} (score {})", export.code, export.score());
+    if let Err(e) = save_json(CHALLENGE_FILE, &export) {
+        println!("Failed to save challenge export: {}", e);
+    }
+    rt.export = Some(export);
+    if let (true, Some(challenge)) = (records.daily_scored, settings.daily.as_ref()) {
+        // Restarts after this are practice
+        records.daily_scored = false;
+        let result = DailyResult {
+            date: challenge.date,
+            code: challenge.code(),
+            human_score: rt.machine.human_score,
+            ai_score: rt.machine.ai_score,
+            score: log.score.as_ref().map_or(0, |s| s.total),
+            forfeit: false,
+        };
+        if records.daily.settle(result) {
+            if let Err(e) = save_json(DAILY_FILE, &records.daily) {
+                println!("Failed to save daily result: {}", e);
+            }
+        }
+    }
+    if let Err(e) = save_json(LAST_MATCH_FILE, &log) {
+        println!("Failed to save match log: {}", e);
+    }
+    let total = |l: &MatchLog| l.score.as_ref().map_or(0, |s| s.total);
+    let best = load_json::<MatchLog>(BEST_MATCH_FILE);
+    if best.is_none_or(|b| total(&log) > total(&b)) {
+        if let Err(e) = save_json(BEST_MATCH_FILE, &log) {
+            println!("Failed to save best match: {}", e);
+        }
+    }
+}
+
+// Report finished matches, simulate AI-vs-AI ones, and load the next human match on tap
+#[cfg(feature = "bevy")]
+fn run_tournament(
+    run: Option<ResMut<TournamentRun>>,
+    mut rt: ResMut<DuelRuntime>,
+    records: Res<Records>,
+    time: Res<Time>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+) {
+    let Some(mut run) = run else { return; };
+    if let Some(id) = run.current {
+        if rt.machine.match_state == MatchState::InProgress { return; }
+        if !run.reported {
+            run.reported = true;
+            let (human, ai) = (rt.machine.human_score, rt.machine.ai_score);
+            let (a, b) = if run.swapped { (ai, human) } else { (human, ai) };
+            if let Err(e) = run.bracket.report(id, a, b) {
+                println!("Failed to report tournament match: {}", e);
+            }
+            run.bracket.simulate_ai_matches();
+            if let Err(e) = save_json(TOURNAMENT_FILE, &run.bracket) {
+                println!("Failed to save tournament: {}", e);
+            }
+        }
+        // The final stays on screen with the champion
+        if run.bracket.is_finished() { return; }
+        if !(mouse.just_pressed(MouseButton::Left) || touches.any_just_pressed()) { return; }
+        run.current = None;
+    }
+    if run.bracket.simulate_ai_matches() > 0 {
+        if let Err(e) = save_json(TOURNAMENT_FILE, &run.bracket) {
+            println!("Failed to save tournament: {}", e);
+        }
+    }
+    let Some(id) = run.bracket.next_match() else { return; };
+    let Some((a, b)) = run.bracket.pairing(id) else { return; };
+    // A human always takes the on-screen human side; the other entrant is AI or player two
+    let swapped = run.bracket.entrants[a].ai.is_some();
+    let opponent = &run.bracket.entrants[if swapped { a } else { b }];
+    rt.hot_seat = opponent.ai.is_none();
+    if let Some(tier) = opponent.ai { rt.ai_profile = tier.profile(); }
+    let cfg = run.bracket.duel_config(id);
+    rt.ai_rng = XorShift32::new(cfg.seed ^ 0xDEADBEEF);
+    rt.ai_plan = None;
+    rt.ai_next_tap_ms = None;
+    let now_ms = (time.elapsed_seconds_f64() * 1000.0) as u64;
+    rt.machine = DuelMachine::new(cfg, now_ms);
+    rt.machine.cue_for_ai = rt.hot_seat;
+    rt.machine.input_offset_us = records.device.input_offset_us();
+    run.current = Some(id);
+    run.swapped = swapped;
+    run.reported = false;
 }
 
 #[cfg(feature = "bevy")]
@@ -329,14 +904,23 @@ fn react_outcomes(
 #[cfg(feature = "bevy")]
 fn react_audio(
     mut go_rx: EventReader<GoCue>,
+    mut cut_rx: EventReader<CutCue>,
+    mut beat_rx: EventReader<BeatCue>,
     mut slash_rx: EventReader<SlashCue>,
     mut clash_rx: EventReader<ClashCue>,
+    mut decoy_rx: EventReader<DecoyCue>,
     handles: Res<AudioHandles>,
     audio: Res<Audio>,
 ) {
     for _ in go_rx.read() {
         if let Some(h) = &handles.go { audio.play(h.clone()); }
     }
+    for _ in cut_rx.read() {
+        if let Some(h) = handles.cut.as_ref().or(handles.go.as_ref()) { audio.play(h.clone()); }
+    }
+    for _ in beat_rx.read() {
+        if let Some(h) = &handles.go { audio.play(h.clone()); }
+    }
     for _ in slash_rx.read() {
         // Draw + hit sequence
         if let Some(d) = &handles.draw { audio.play(d.clone()); }
@@ -345,4 +929,127 @@ fn react_audio(
     for _ in clash_rx.read() {
         if let Some(c) = &handles.clash { audio.play(c.clone()); }
     }
+    for ev in decoy_rx.read() {
+        if ev.kind != DecoyKind::Sound { continue; }
+        // Fall back to a quiet draw when no dedicated decoy sample ships
+        if let Some(d) = &handles.decoy { audio.play(d.clone()); }
+        else if let Some(d) = &handles.draw { audio.play(d.clone()).with_volume(0.5); }
+    }
+}
+
+// Metronome: a beat every CALIBRATION_BEAT_MS, stamped when it is shown like GO; any tap counts
+#[cfg(feature = "bevy")]
+#[allow(clippy::too_many_arguments)]
+fn run_calibration(
+    mut run: ResMut<CalibrationRun>,
+    mut records: ResMut<Records>,
+    settings: Res<IaidoSettings>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mut touches: EventReader<TouchInput>,
+    mut beat_tx: EventWriter<BeatCue>,
+    time: Res<Time>,
+) {
+    let started = touches.read().filter(|t| t.phase == bevy::input::touch::TouchPhase::Started).count();
+    if run.finished { return; }
+    let now_us = time.elapsed().as_micros() as u64;
+    if run.beat_pending {
+        run.beat_pending = false;
+        run.cal.beat(now_us + u64::from(settings.audio_latency_ms) * 1000);
+    }
+    if started > 0 || keys.get_just_pressed().next().is_some() || mouse_buttons.just_pressed(MouseButton::Left) {
+        run.cal.tap(now_us);
+    }
+    if run.cal.is_done() {
+        run.finished = true;
+        run.result = run.cal.estimate();
+        match run.result {
+            Some(est) => {
+                records.device.latency = Some(est);
+                if let Err(e) = save_json(DEVICE_FILE, &records.device) { println!("Failed to save calibration: {}", e); }
+                println!(
+                    "Input offset {:+.1} ms, jitter {:.1} ms over {} taps",
+                    est.offset_us as f64 / 1000.0,
+                    est.jitter_us as f64 / 1000.0,
+                    est.trials,
+                );
+            }
+            None => println!("Too few or too uneven taps on the beat; calibration not saved"),
+        }
+        return;
+    }
+    let now_ms = now_us / 1000;
+    // One beat of lead-in before the first
+    if run.next_beat_ms == 0 { run.next_beat_ms = now_ms + CALIBRATION_BEAT_MS; }
+    if now_ms >= run.next_beat_ms {
+        // Stay on the grid unless a stalled frame skipped past a beat
+        run.next_beat_ms = (run.next_beat_ms + CALIBRATION_BEAT_MS).max(now_ms);
+        run.beat_pending = true;
+        beat_tx.send(BeatCue);
+    }
+}
+
+// DPI follows the window's scale factor (it can arrive late on mobile) unless fixed in settings
+#[cfg(feature = "bevy")]
+fn update_dpi(
+    windows: Query<&Window, (With<bevy::window::PrimaryWindow>, Changed<Window>)>,
+    settings: Res<IaidoSettings>,
+    records: Res<Records>,
+    rt: Option<ResMut<DuelRuntime>>,
+    swipe_run: Option<ResMut<SwipeCalibrationRun>>,
+) {
+    let Ok(window) = windows.get_single() else { return; };
+    let res = &window.resolution;
+    let screen_mm = settings.screen_mm.or(records.device.screen_mm);
+    let dpi = settings.dpi.unwrap_or_else(|| detect_dpi(res.scale_factor(), screen_mm.map(|mm| (res.physical_width() as f32, mm))));
+    if let Some(mut rt) = rt { rt.cfg.dpi = dpi; }
+    if let Some(mut run) = swipe_run { run.cal.dpi = dpi; }
+}
+
+// Measure comfortable swipes (first finger, or mouse drags) until there are enough to fit
+#[cfg(feature = "bevy")]
+#[allow(clippy::too_many_arguments)]
+fn run_swipe_calibration(
+    mut run: ResMut<SwipeCalibrationRun>,
+    mut records: ResMut<Records>,
+    mut rt: ResMut<DuelRuntime>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mut mouse_motion: EventReader<MouseMotion>,
+    mut touches: EventReader<TouchInput>,
+    time: Res<Time>,
+) {
+    use bevy::input::touch::TouchPhase;
+    let motion: Vec2 = mouse_motion.read().map(|m| m.delta).sum();
+    let run = &mut *run;
+    let mut delta = if mouse_buttons.pressed(MouseButton::Left) { motion } else { Vec2::ZERO };
+    let mut released = mouse_buttons.just_released(MouseButton::Left);
+    for ev in touches.read() {
+        match ev.phase {
+            TouchPhase::Started if run.finger.is_none() => run.finger = Some((ev.id, ev.position)),
+            TouchPhase::Moved => {
+                if let Some((_, last)) = run.finger.as_mut().filter(|(id, _)| *id == ev.id) {
+                    delta += ev.position - *last;
+                    *last = ev.position;
+                }
+            }
+            TouchPhase::Ended | TouchPhase::Canceled if run.finger.is_some_and(|(id, _)| id == ev.id) => {
+                run.finger = None;
+                released = true;
+            }
+            _ => {}
+        }
+    }
+    if run.finished { return; }
+    let dt_ms = (time.delta_seconds_f64() * 1000.0) as u64;
+    run.cal.motion(dt_ms, delta.x, delta.y);
+    if released { run.cal.release(); }
+    if !run.cal.is_done() { return; }
+    run.finished = true;
+    run.result = run.cal.estimate();
+    if let Some(est) = run.result {
+        records.device.swipe = Some(est);
+        if let Err(e) = save_json(DEVICE_FILE, &records.device) { println!("Failed to save calibration: {}", e); }
+        rt.cfg = records.device.swipe_config(rt.cfg.dpi);
+        println!("Min swipe {:.1} mm, direction lock {} ms over {} swipes", est.min_swipe_mm, est.lock_ms, est.swipes);
+    }
 }
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+
+use crate::config::{RATING_HISTORY_MAX, RATING_K, RATING_K_PROVISIONAL, RATING_PROVISIONAL_GAMES, RATING_START};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RatingEntry {
+    pub before: i32,
+    pub after: i32,
+    pub opponent_rating: i32,
+    pub won: bool,
+    /// Unix seconds; 0 if the clock was unavailable.
+    pub ts_s: u64,
+}
+
+impl RatingEntry {
+    pub fn delta(&self) -> i32 { self.after - self.before }
+}
+
+/// Local player profile: Elo rating from matches against rated AI opponents.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PlayerProfile {
+    pub rating: i32,
+    pub games: u32,
+    pub wins: u32,
+    /// Most recent last, capped at RATING_HISTORY_MAX.
+    #[serde(default)]
+    pub history: Vec<RatingEntry>,
+}
+
+impl Default for PlayerProfile {
+    fn default() -> Self { Self { rating: RATING_START, games: 0, wins: 0, history: Vec::new() } }
+}
+
+/// Chance (0..1) that `rating` beats `opponent` under Elo.
+pub fn expected_score(rating: i32, opponent: i32) -> f64 {
+    1.0 / (1.0 + 10f64.powf(f64::from(opponent - rating) / 400.0))
+}
+
+impl PlayerProfile {
+    pub fn k_factor(&self) -> i32 {
+        if self.games < RATING_PROVISIONAL_GAMES { RATING_K_PROVISIONAL } else { RATING_K }
+    }
+
+    /// Apply one match result against an opponent of fixed rating; returns the entry added.
+    pub fn record(&mut self, opponent_rating: i32, won: bool, ts_s: u64) -> RatingEntry {
+        let actual = if won { 1.0 } else { 0.0 };
+        let delta = (f64::from(self.k_factor()) * (actual - expected_score(self.rating, opponent_rating))).round() as i32;
+        let entry = RatingEntry { before: self.rating, after: self.rating + delta, opponent_rating, won, ts_s };
+        self.rating = entry.after;
+        self.games += 1;
+        self.wins += u32::from(won);
+        self.history.push(entry.clone());
+        if self.history.len() > RATING_HISTORY_MAX {
+            let extra = self.history.len() - RATING_HISTORY_MAX;
+            self.history.drain(..extra);
+        }
+        entry
+    }
+}
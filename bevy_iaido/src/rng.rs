@@ -4,6 +4,8 @@ pub struct XorShift32 { state: u32 }
 
 impl XorShift32 {
     pub fn new(seed: u32) -> Self { Self { state: seed.max(1) } }
+    /// Current state; `XorShift32::new(state)` resumes the sequence from here.
+    pub fn state(&self) -> u32 { self.state }
     pub fn next_u32(&mut self) -> u32 {
         let mut x = self.state;
         x ^= x << 13;
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+use crate::config::*;
+use crate::types::{Outcome, RoundResult};
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Grade { S, A, B, C }
+
+impl Grade {
+    fn base_points(self) -> u32 {
+        match self { Grade::S => 100, Grade::A => 75, Grade::B => 50, Grade::C => 25 }
+    }
+
+    fn bump(self) -> Grade {
+        match self { Grade::S | Grade::A => Grade::S, Grade::B => Grade::A, Grade::C => Grade::B }
+    }
+}
+
+impl std::fmt::Display for Grade {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self { Grade::S => "S", Grade::A => "A", Grade::B => "B", Grade::C => "C" };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RoundScore {
+    pub grade: Grade,
+    /// Margin over the opponent's reaction; 0 when the opponent missed or forfeited.
+    pub margin_ms: u32,
+    /// Consecutive human wins including this one.
+    pub streak: u32,
+    pub points: u32,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MatchScore {
+    /// One entry per round result; `None` for rounds the human did not win.
+    pub rounds: Vec<Option<RoundScore>>,
+    pub total: u32,
+    pub best_streak: u32,
+}
+
+fn reaction_grade(ms: u32) -> Grade {
+    if ms <= STYLE_S_MS { Grade::S }
+    else if ms <= STYLE_A_MS { Grade::A }
+    else if ms <= STYLE_B_MS { Grade::B }
+    else { Grade::C }
+}
+
+// Draw-then-cut rounds are graded on draw + cut
+fn total_ms(reaction: Option<u32>, draw: Option<u32>) -> Option<u32> {
+    reaction.map(|r| r + draw.unwrap_or(0))
+}
+
+/// Grade a round won by the human. Forfeit wins (opponent early or wrong) are graded
+/// on the human's own reaction, C if they never swung, and earn no margin.
+pub fn grade_round(result: &RoundResult, streak: u32) -> Option<RoundScore> {
+    if !matches!(result.outcome, Outcome::HumanWin | Outcome::WrongAi | Outcome::EarlyAi) {
+        return None;
+    }
+    let human = total_ms(result.human_reaction_ms, result.human_draw_ms);
+    let ai = total_ms(result.ai_reaction_ms, result.ai_draw_ms);
+    let margin_ms = match (result.outcome, human, ai) {
+        (Outcome::HumanWin, Some(h), Some(a)) => a.saturating_sub(h),
+        _ => 0,
+    };
+    let mut grade = human.map_or(Grade::C, reaction_grade);
+    if margin_ms >= STYLE_MARGIN_BUMP_MS { grade = grade.bump(); }
+    let streak = streak.max(1);
+    let multiplier_pct = (100 + STYLE_STREAK_STEP_PCT * (streak - 1)).min(STYLE_STREAK_MAX_PCT);
+    let raw = grade.base_points() + margin_ms.min(STYLE_MARGIN_CAP_MS) / 3;
+    Some(RoundScore { grade, margin_ms, streak, points: raw * multiplier_pct / 100 })
+}
+
+/// Score a whole match. Losses reset the streak; clashes leave it untouched.
+pub fn score_match(results: &[RoundResult]) -> MatchScore {
+    let mut score = MatchScore::default();
+    let mut streak = 0;
+    for result in results {
+        match result.outcome {
+            Outcome::HumanWin | Outcome::WrongAi | Outcome::EarlyAi => streak += 1,
+            Outcome::AiWin | Outcome::WrongHuman | Outcome::EarlyHuman => streak = 0,
+            Outcome::Clash => {}
+        }
+        let round = grade_round(result, streak);
+        if let Some(r) = &round { score.total += r.points; }
+        score.best_streak = score.best_streak.max(streak);
+        score.rounds.push(round);
+    }
+    score
+}
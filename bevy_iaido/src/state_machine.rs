@@ -1,4 +1,4 @@
-use crate::combat::judge_outcome;
+use crate::combat::{correct_direction_for, judge_combo, judge_outcome_with, SpeedCheck};
 use crate::config::*;
 use crate::rng::XorShift32;
 use crate::types::*;
@@ -7,10 +7,72 @@ use crate::types::*;
 pub struct DuelConfig {
     pub seed: u32,
     pub clash: bool,
+    /// Fire false cues (flash, sound, opponent twitch) during RandomDelay.
+    pub decoys: bool,
+    /// How long the human's opening stays visible before GO.
+    pub reveal: RevealMode,
+    /// Swipes per opening sequence; 1 is the classic single-swipe round.
+    pub combo_len: u8,
+    pub combo_timing: ComboTiming,
+    /// Resolve clashes with a sword-lock struggle instead of an immediate rematch.
+    pub struggle: bool,
+    /// Carry posture across rounds; a broken posture is penalised next round.
+    pub posture: bool,
+    pub posture_penalty: PosturePenalty,
+    /// Split each round into a draw (any swipe after GO) and a cut on a second cue.
+    pub draw_cut: bool,
+    pub human_handicap: Handicap,
+    pub ai_handicap: Handicap,
+    pub format: MatchFormat,
+    /// Openings to draw from (training drills); empty means all of them.
+    pub drill: Vec<Opening>,
+    /// Tightening schedule used by `MatchFormat::Survival`.
+    pub survival: SurvivalSchedule,
 }
 
 impl Default for DuelConfig {
-    fn default() -> Self { Self { seed: 0xA1D0_5EED, clash: true } }
+    fn default() -> Self {
+        Self {
+            seed: 0xA1D0_5EED,
+            clash: true,
+            decoys: false,
+            reveal: RevealMode::Always,
+            combo_len: 1,
+            combo_timing: ComboTiming::Total,
+            struggle: false,
+            posture: false,
+            posture_penalty: PosturePenalty::ShrinkWindow,
+            draw_cut: false,
+            human_handicap: Handicap::default(),
+            ai_handicap: Handicap::default(),
+            format: MatchFormat::default(),
+            drill: Vec::new(),
+            survival: SurvivalSchedule::default(),
+        }
+    }
+}
+
+impl Default for MatchFormat {
+    fn default() -> Self { MatchFormat::FirstTo(ROUNDS_TO_WIN) }
+}
+
+impl Default for SurvivalSchedule {
+    fn default() -> Self {
+        Self {
+            window_start_ms: INPUT_WINDOW_MS,
+            window_step_ms: SURVIVAL_WINDOW_STEP_MS,
+            window_min_ms: SURVIVAL_MIN_WINDOW_MS,
+            tie_start_ms: SURVIVAL_TIE_START_MS,
+            tie_step_ms: SURVIVAL_TIE_STEP_MS,
+            tie_min_ms: SURVIVAL_MIN_TIE_MS,
+            ai_step_ms: SURVIVAL_AI_STEP_MS,
+            ai_min_reaction_ms: SURVIVAL_AI_MIN_REACTION_MS,
+        }
+    }
+}
+
+impl Default for PostureState {
+    fn default() -> Self { Self { human: POSTURE_MAX, ai: POSTURE_MAX, human_broken: false, ai_broken: false } }
 }
 
 #[derive(Clone, Debug)]
@@ -18,8 +80,14 @@ pub struct DuelMachine {
     pub phase: DuelPhase,
     pub rng: XorShift32,
     pub seed: u32,
+    pub cfg: DuelConfig,
     pub human_opening: Opening,
     pub ai_opening: Opening,
+    /// Full opening sequences in combo rounds (first entry mirrors `*_opening`); empty otherwise.
+    pub human_combo: Vec<Opening>,
+    pub ai_combo: Vec<Opening>,
+    pub human_steps: Vec<SwipeEvent>,
+    pub ai_steps: Vec<SwipeEvent>,
     pub go_ts_ms: Option<u64>,
     pub phase_start_ms: u64,
     pub delay_target_ms: Option<u64>,
@@ -30,20 +98,56 @@ pub struct DuelMachine {
     pub match_state: MatchState,
     pub human_score: u8,
     pub ai_score: u8,
+    /// Human round wins this match, uncounted by the score cap (survival runs can go long).
+    pub human_wins: u32,
     pub input_window_ms: u64,
+    /// Speed-check tie margin for the current round (tightens in survival).
+    pub tie_window_ms: u64,
+    pub pending_decoy: Option<DecoyEvent>,
+    pub fired_decoy: Option<DecoyEvent>,
+    pub struggle_meter: i32,
+    pub struggle_taps: Vec<StruggleTap>,
+    pub posture: PostureState,
+    pub human_draw: Option<SwipeEvent>,
+    pub ai_draw: Option<SwipeEvent>,
+    pub cut_ts_ms: Option<u64>,
+    /// Forgiven wrong inputs left this match (handicap), and the ones dropped this round.
+    pub human_forgives: u8,
+    pub ai_forgives: u8,
+    pub human_forgiven: Option<SwipeEvent>,
+    pub ai_forgiven: Option<SwipeEvent>,
+    /// When this round's GO reached the player, stamped by the front end after the fact.
+    pub go_presented: Option<GoPresentation>,
+    /// Measure the AI side from the presented cue too (a second human in hot-seat).
+    pub cue_for_ai: bool,
+    /// Calibrated device input latency, taken off the same sides' reactions.
+    pub input_offset_us: u64,
+    // RNG state the current round's openings were picked from
+    round_seed: u32,
+    // First press of a key chord each side is still collecting (see `hold_for_chord`)
+    human_chord_ms: Option<u64>,
+    ai_chord_ms: Option<u64>,
+    // Memory reveal: this round's openings were rolled at Standoff, and whether it is a clash rematch
+    rolled_at_standoff: bool,
+    standoff_clash: bool,
 }
 
 impl DuelMachine {
     pub fn new(cfg: DuelConfig, start_ms: u64) -> Self {
         let mut rng = XorShift32::new(cfg.seed);
-        let human_opening = pick_opening(&mut rng);
-        let ai_opening = pick_opening(&mut rng);
-        Self {
+        let human_opening = pick_opening_from(&mut rng, &cfg.drill);
+        let ai_opening = pick_opening_from(&mut rng, &cfg.drill);
+        let mut dm = Self {
             phase: DuelPhase::Standoff,
             rng,
             seed: cfg.seed,
+            cfg,
             human_opening,
             ai_opening,
+            human_combo: Vec::new(),
+            ai_combo: Vec::new(),
+            human_steps: Vec::new(),
+            ai_steps: Vec::new(),
             go_ts_ms: None,
             phase_start_ms: start_ms,
             delay_target_ms: None,
@@ -54,7 +158,219 @@ impl DuelMachine {
             match_state: MatchState::InProgress,
             human_score: 0,
             ai_score: 0,
+            human_wins: 0,
             input_window_ms: INPUT_WINDOW_MS,
+            tie_window_ms: TIE_WINDOW_MS,
+            pending_decoy: None,
+            fired_decoy: None,
+            struggle_meter: 0,
+            struggle_taps: Vec::new(),
+            posture: PostureState::default(),
+            human_draw: None,
+            ai_draw: None,
+            cut_ts_ms: None,
+            human_forgives: 0,
+            ai_forgives: 0,
+            human_forgiven: None,
+            ai_forgiven: None,
+            go_presented: None,
+            cue_for_ai: false,
+            input_offset_us: 0,
+            round_seed: 0,
+            human_chord_ms: None,
+            ai_chord_ms: None,
+            rolled_at_standoff: false,
+            standoff_clash: false,
+        };
+        dm.round_seed = dm.seed;
+        dm.rolled_at_standoff = dm.cfg.reveal == RevealMode::Memory;
+        dm.human_forgives = u8::from(dm.cfg.human_handicap.forgive_wrong);
+        dm.ai_forgives = u8::from(dm.cfg.ai_handicap.forgive_wrong);
+        dm.roll_combos();
+        dm
+    }
+
+    fn is_combo(&self) -> bool { self.cfg.combo_len > 1 }
+
+    // Extend the freshly picked openings into sequences; no RNG use in single-swipe rounds
+    fn roll_combos(&mut self) {
+        self.human_combo.clear();
+        self.ai_combo.clear();
+        if !self.is_combo() { return; }
+        let len = self.cfg.combo_len.min(MAX_COMBO_LEN);
+        self.human_combo.push(self.human_opening);
+        self.ai_combo.push(self.ai_opening);
+        for _ in 1..len {
+            let h = pick_opening_from(&mut self.rng, &self.cfg.drill);
+            let a = pick_opening_from(&mut self.rng, &self.cfg.drill);
+            self.human_combo.push(h);
+            self.ai_combo.push(a);
+        }
+    }
+
+    /// Opening the actor has to answer next (the pending combo step in combo rounds).
+    pub fn next_opening(&self, actor: Actor) -> Opening {
+        let (single, combo, steps) = match actor {
+            Actor::Human => (self.human_opening, &self.human_combo, &self.human_steps),
+            Actor::Ai => (self.ai_opening, &self.ai_combo, &self.ai_steps),
+        };
+        combo.get(steps.len()).copied().unwrap_or(single)
+    }
+
+    pub fn steps(&self, actor: Actor) -> &[SwipeEvent] {
+        match actor { Actor::Human => &self.human_steps, Actor::Ai => &self.ai_steps }
+    }
+
+    fn actor_done(&self, actor: Actor) -> bool {
+        match actor { Actor::Human => self.human_swipe.is_some(), Actor::Ai => self.ai_swipe.is_some() }
+    }
+
+    // Latest accepted timestamp for the actor's next swipe
+    fn step_deadline(&self, actor: Actor) -> u64 {
+        let go = self.input_origin_ms();
+        match self.steps(actor).last() {
+            Some(prev) => prev.ts_ms + COMBO_STEP_WINDOW_MS,
+            None => go + self.input_window_for(actor),
+        }
+    }
+
+    // Reaction times count from the cut cue in draw-then-cut rounds, from GO otherwise
+    fn input_origin_ms(&self) -> u64 {
+        self.cut_ts_ms.or(self.go_ts_ms).unwrap_or(self.phase_start_ms)
+    }
+
+    /// Record when this round's GO was presented; only the first stamp after GO counts.
+    pub fn present_go(&mut self, presented: GoPresentation) {
+        if self.go_ts_ms.is_some() && self.go_presented.is_none() {
+            self.go_presented = Some(presented);
+        }
+    }
+
+    // How long after GO the cue reached the actor, plus their input latency; the AI reads GO
+    // straight off the machine
+    fn cue_delay_us(&self, actor: Actor) -> u64 {
+        if actor == Actor::Ai && !self.cue_for_ai { return 0; }
+        let shown = self.go_presented.zip(self.go_ts_ms).map_or(0, |(p, go)| p.cue_us().saturating_sub(go * 1000));
+        shown + self.input_offset_us
+    }
+
+    // When the actor's first input landed, if it beat the cue to them: they anticipated GO
+    fn beat_cue_us(&self, actor: Actor) -> Option<u64> {
+        let (draw, first, swipe) = match actor {
+            Actor::Human => (&self.human_draw, self.human_steps.first(), &self.human_swipe),
+            Actor::Ai => (&self.ai_draw, self.ai_steps.first(), &self.ai_swipe),
+        };
+        let input = if self.cfg.draw_cut { draw.as_ref() } else { first.or(swipe.as_ref()) }?;
+        let cue_us = self.go_ts_ms? * 1000 + self.cue_delay_us(actor);
+        (input.ts_us() < cue_us).then_some(input.ts_us())
+    }
+
+    pub fn has_drawn(&self, actor: Actor) -> bool {
+        match actor { Actor::Human => self.human_draw.is_some(), Actor::Ai => self.ai_draw.is_some() }
+    }
+
+    /// True while the actor has drawn and must hold still until the cut cue.
+    pub fn awaiting_cut(&self, actor: Actor) -> bool {
+        self.cfg.draw_cut && self.cut_ts_ms.is_none() && self.has_drawn(actor)
+    }
+
+    /// Rounds survived so far (human round wins) in survival; 0 in other formats.
+    pub fn survival_round(&self) -> u32 {
+        if self.cfg.format == MatchFormat::Survival { self.human_wins } else { 0 }
+    }
+
+    fn base_window_ms(&self) -> u64 {
+        match self.cfg.format {
+            MatchFormat::Survival => self.cfg.survival.window_at(self.survival_round()),
+            _ => INPUT_WINDOW_MS,
+        }
+    }
+
+    fn base_tie_ms(&self) -> u64 {
+        match self.cfg.format {
+            MatchFormat::Survival => self.cfg.survival.tie_at(self.survival_round()),
+            _ => TIE_WINDOW_MS,
+        }
+    }
+
+    pub fn handicap(&self, actor: Actor) -> Handicap {
+        match actor { Actor::Human => self.cfg.human_handicap, Actor::Ai => self.cfg.ai_handicap }
+    }
+
+    /// Input window for one side, shortened while that side's posture is broken
+    /// and widened by its handicap.
+    pub fn input_window_for(&self, actor: Actor) -> u64 {
+        let broken = match actor { Actor::Human => self.posture.human_broken, Actor::Ai => self.posture.ai_broken };
+        let window = if self.cfg.posture && broken && self.cfg.posture_penalty == PosturePenalty::ShrinkWindow {
+            self.input_window_ms.saturating_sub(POSTURE_BROKEN_WINDOW_CUT_MS).max(CLASH_INPUT_WINDOW_MS)
+        } else {
+            self.input_window_ms
+        };
+        window + self.handicap(actor).extra_window_ms
+    }
+
+    fn speed_check(&self) -> SpeedCheck {
+        let mut check = SpeedCheck {
+            human_extra_tie_ms: self.cfg.human_handicap.extra_tie_ms,
+            ai_extra_tie_ms: self.cfg.ai_handicap.extra_tie_ms,
+            human_offset_ms: self.cfg.human_handicap.reaction_offset_ms,
+            ai_offset_ms: self.cfg.ai_handicap.reaction_offset_ms,
+            human_cue_us: self.cue_delay_us(Actor::Human),
+            ai_cue_us: self.cue_delay_us(Actor::Ai),
+        };
+        if self.cfg.posture && self.cfg.posture_penalty == PosturePenalty::WidenOpponentTie {
+            if self.posture.human_broken { check.ai_extra_tie_ms += POSTURE_BROKEN_TIE_BONUS_MS; }
+            if self.posture.ai_broken { check.human_extra_tie_ms += POSTURE_BROKEN_TIE_BONUS_MS; }
+        }
+        check
+    }
+
+    // Drain posture from this round's result; breaks carry into the next round only
+    fn settle_posture(&mut self, outcome: Outcome, human_r: Option<u64>, ai_r: Option<u64>) -> Option<PostureState> {
+        if !self.cfg.posture { return None; }
+        let mut human_drain = 0u8;
+        let mut ai_drain = 0u8;
+        match outcome {
+            Outcome::WrongHuman if human_r.is_some_and(|r| r < POSTURE_WRONG_FAST_MS) => human_drain = POSTURE_WRONG_DRAIN,
+            Outcome::WrongAi if ai_r.is_some_and(|r| r < POSTURE_WRONG_FAST_MS) => ai_drain = POSTURE_WRONG_DRAIN,
+            Outcome::Clash => {
+                human_drain = POSTURE_CLASH_DRAIN;
+                ai_drain = POSTURE_CLASH_DRAIN;
+            }
+            Outcome::HumanWin | Outcome::AiWin => {
+                if let (Some(h), Some(a)) = (human_r, ai_r) {
+                    if h.abs_diff(a) <= POSTURE_NEAR_TIE_MS {
+                        if outcome == Outcome::HumanWin { ai_drain = POSTURE_NEAR_TIE_DRAIN; } else { human_drain = POSTURE_NEAR_TIE_DRAIN; }
+                    }
+                }
+            }
+            _ => {}
+        }
+        let p = &mut self.posture;
+        p.human = p.human.saturating_sub(human_drain);
+        p.ai = p.ai.saturating_sub(ai_drain);
+        p.human_broken = p.human == 0;
+        p.ai_broken = p.ai == 0;
+        // A break is spent on the next round, then the guard recovers
+        if p.human_broken { p.human = POSTURE_MAX; }
+        if p.ai_broken { p.ai = POSTURE_MAX; }
+        Some(*p)
+    }
+
+    fn input_expired(&self, now_ms: u64) -> bool {
+        [Actor::Human, Actor::Ai].iter().all(|&a| {
+            // A chord opened in time will be stamped in time; wait for it to commit
+            let chord = match a { Actor::Human => self.human_chord_ms, Actor::Ai => self.ai_chord_ms };
+            self.actor_done(a) || (now_ms > self.step_deadline(a) && chord.is_none_or(|t| t > self.step_deadline(a)))
+        })
+    }
+
+    /// Key chord the actor's keys are still collecting, by its first press (`None` when closed).
+    /// The chord commits stamped at that press, so the input window stays open until it does.
+    pub fn hold_for_chord(&mut self, actor: Actor, first_press_ms: Option<u64>) {
+        match actor {
+            Actor::Human => self.human_chord_ms = first_press_ms,
+            Actor::Ai => self.ai_chord_ms = first_press_ms,
         }
     }
 
@@ -74,23 +390,89 @@ impl DuelMachine {
         self.phase = DuelPhase::RandomDelay;
         self.phase_start_ms = now_ms;
         self.go_ts_ms = None;
+        self.go_presented = None;
         self.human_swipe = None;
         self.ai_swipe = None;
-        self.input_window_ms = if clash { CLASH_INPUT_WINDOW_MS } else { INPUT_WINDOW_MS };
-        self.human_opening = pick_opening(&mut self.rng);
-        self.ai_opening = pick_opening(&mut self.rng);
+        self.human_steps.clear();
+        self.ai_steps.clear();
+        self.human_draw = None;
+        self.ai_draw = None;
+        self.cut_ts_ms = None;
+        self.human_forgiven = None;
+        self.ai_forgiven = None;
+        self.human_chord_ms = None;
+        self.ai_chord_ms = None;
+        self.input_window_ms = if clash { CLASH_INPUT_WINDOW_MS } else { self.base_window_ms() };
+        self.tie_window_ms = self.base_tie_ms();
+        if !std::mem::take(&mut self.rolled_at_standoff) { self.roll_openings(); }
         let delay = if clash { self.schedule_clash_delay() } else { self.schedule_go_delay() };
         self.delay_target_ms = Some(now_ms + delay);
+        // Decoys only roll the RNG when enabled so default replays stay identical
+        self.pending_decoy = if self.cfg.decoys && !clash { self.schedule_decoy(now_ms, delay) } else { None };
     }
 
+    /// Memory reveal: pause in Standoff before the next round, rolling its openings now so they can flash.
+    fn enter_standoff(&mut self, now_ms: u64, clash: bool) {
+        self.phase = DuelPhase::Standoff;
+        self.phase_start_ms = now_ms;
+        self.standoff_clash = clash;
+        self.roll_openings();
+        self.rolled_at_standoff = true;
+    }
+
+    fn roll_openings(&mut self) {
+        self.round_seed = self.rng.state();
+        self.human_opening = pick_opening_from(&mut self.rng, &self.cfg.drill);
+        self.ai_opening = pick_opening_from(&mut self.rng, &self.cfg.drill);
+        self.roll_combos();
+    }
+
+    fn schedule_decoy(&mut self, now_ms: u64, delay: u64) -> Option<DecoyEvent> {
+        if delay < DECOY_MIN_AFTER_MS + DECOY_MIN_LEAD_MS { return None; }
+        if self.rng.next_u32() % 100 >= DECOY_CHANCE_PERCENT { return None; }
+        let offset = self.rng.range_u64(DECOY_MIN_AFTER_MS, delay - DECOY_MIN_LEAD_MS);
+        let kind = match self.rng.next_u32() % 3 {
+            0 => DecoyKind::Flash,
+            1 => DecoyKind::Sound,
+            _ => DecoyKind::Twitch,
+        };
+        Some(DecoyEvent { kind, ts_ms: now_ms + offset })
+    }
+
+    /// Whether presentation layers may show the human's opening right now.
+    /// In memory mode every round opens with a Standoff, where the flash plays.
+    pub fn opening_visible(&self, now_ms: u64) -> bool {
+        let before_go = matches!(self.phase, DuelPhase::Standoff | DuelPhase::RandomDelay);
+        match self.cfg.reveal {
+            RevealMode::Always => true,
+            RevealMode::AtGo => !before_go,
+            RevealMode::Memory => match self.phase {
+                DuelPhase::Standoff => now_ms.saturating_sub(self.phase_start_ms) < MEMORY_FLASH_MS,
+                DuelPhase::RandomDelay => false,
+                _ => true,
+            },
+        }
+    }
+
+    /// Decoy fired since the last call, if any. Presentation layers poll this once per frame.
+    pub fn take_fired_decoy(&mut self) -> Option<DecoyEvent> { self.fired_decoy.take() }
+
     pub fn tick(&mut self, now_ms: u64) {
         match self.phase {
             DuelPhase::Standoff => {
-                if now_ms - self.phase_start_ms >= START_DELAY_MS {
-                    self.start_round(now_ms);
+                // Only the match opener waits the full delay; memory-mode rounds after it pause briefly
+                let wait = if self.round_results.is_empty() { START_DELAY_MS } else { MEMORY_STANDOFF_MS };
+                if now_ms - self.phase_start_ms >= wait {
+                    let clash = std::mem::take(&mut self.standoff_clash);
+                    self.enter_random_delay(now_ms, clash);
                 }
             }
             DuelPhase::RandomDelay => {
+                if let Some(decoy) = &self.pending_decoy {
+                    if now_ms >= decoy.ts_ms {
+                        self.fired_decoy = self.pending_decoy.take();
+                    }
+                }
                 if let Some(target) = self.delay_target_ms {
                     if now_ms >= target {
                         self.phase = DuelPhase::GoSignal;
@@ -101,22 +483,51 @@ impl DuelMachine {
                 }
             }
             DuelPhase::GoSignal => {
+                self.phase = if self.cfg.draw_cut { DuelPhase::DrawWindow } else { DuelPhase::InputWindow };
+                self.phase_start_ms = now_ms;
+            }
+            DuelPhase::DrawWindow => {
+                let both = self.human_draw.is_some() && self.ai_draw.is_some();
+                if both || now_ms - self.phase_start_ms >= DRAW_WINDOW_MS {
+                    self.finish_draw(now_ms);
+                }
+            }
+            DuelPhase::CutDelay => {
+                if let Some(target) = self.delay_target_ms {
+                    if now_ms >= target {
+                        self.phase = DuelPhase::CutSignal;
+                        self.cut_ts_ms = Some(now_ms);
+                        self.phase_start_ms = now_ms;
+                        self.delay_target_ms = None;
+                    }
+                }
+            }
+            DuelPhase::CutSignal => {
                 self.phase = DuelPhase::InputWindow;
                 self.phase_start_ms = now_ms;
             }
             DuelPhase::InputWindow => {
-                if self.human_swipe.is_some() && self.ai_swipe.is_some() {
+                if self.input_expired(now_ms) {
                     let outcome = self.resolve(now_ms);
-                    self.apply_outcome(outcome);
-                    self.phase = DuelPhase::ResultFlash;
-                    self.phase_start_ms = now_ms;
+                    self.apply_outcome(outcome, now_ms);
+                    if self.phase != DuelPhase::Struggle {
+                        self.phase = DuelPhase::ResultFlash;
+                        self.phase_start_ms = now_ms;
+                    }
                 }
             }
             DuelPhase::Resolution => {
                 let outcome = self.resolve(now_ms);
-                self.apply_outcome(outcome);
-                self.phase = DuelPhase::ResultFlash;
-                self.phase_start_ms = now_ms;
+                self.apply_outcome(outcome, now_ms);
+                if self.phase != DuelPhase::Struggle {
+                    self.phase = DuelPhase::ResultFlash;
+                    self.phase_start_ms = now_ms;
+                }
+            }
+            DuelPhase::Struggle => {
+                if now_ms - self.phase_start_ms >= STRUGGLE_MS {
+                    self.resolve_struggle(now_ms);
+                }
             }
             DuelPhase::ResultFlash => {
                 if now_ms - self.phase_start_ms >= 300 { // ≤300 ms flash
@@ -128,151 +539,512 @@ impl DuelMachine {
                 if self.match_state != MatchState::InProgress {
                     self.phase = DuelPhase::Finished;
                 } else if now_ms - self.phase_start_ms >= 500 { // ≤500 ms reset
-                    self.enter_random_delay(now_ms, false);
+                    if self.cfg.reveal == RevealMode::Memory {
+                        self.enter_standoff(now_ms, false);
+                    } else {
+                        self.enter_random_delay(now_ms, false);
+                    }
                 }
             }
             DuelPhase::Finished | DuelPhase::Reset => {}
         }
     }
 
+    /// A swipe timed by the frame it was read in; its reaction is kept to the millisecond.
     pub fn on_swipe(&mut self, actor: Actor, dir: Direction, ts_ms: u64) {
+        self.on_swipe_event(actor, SwipeEvent::at_ms(dir, ts_ms));
+    }
+
+    /// `on_swipe` with the input's own microsecond timestamp, on the same clock as `tick`.
+    pub fn on_swipe_us(&mut self, actor: Actor, dir: Direction, ts_us: u64) {
+        self.on_swipe_event(actor, SwipeEvent::at_us(dir, ts_us));
+    }
+
+    /// `on_swipe` for a recorded event, keeping whatever precision it was timed with.
+    pub fn on_swipe_event(&mut self, actor: Actor, ev: SwipeEvent) {
+        let ts_ms = ev.ts_ms;
         // Only process inputs in active phases
-        if !matches!(self.phase, DuelPhase::RandomDelay | DuelPhase::GoSignal | DuelPhase::InputWindow) {
+        if !matches!(
+            self.phase,
+            DuelPhase::RandomDelay | DuelPhase::GoSignal | DuelPhase::DrawWindow | DuelPhase::CutDelay | DuelPhase::CutSignal | DuelPhase::InputWindow
+        ) {
             return;
         }
 
-        // Any swipe before GO is instant loss for that actor
-        if let Some(go) = self.go_ts_ms {
-            if ts_ms < go {
-                let outcome = match actor { Actor::Human => Outcome::EarlyHuman, Actor::Ai => Outcome::EarlyAi };
-                self.round_results.push(RoundResult { 
-                    human_opening: self.human_opening, 
-                    ai_opening: self.ai_opening, 
-                    outcome, 
-                    human_reaction_ms: None, 
-                    ai_reaction_ms: None 
-                });
-                self.phase = DuelPhase::ResultFlash;
-                self.phase_start_ms = ts_ms;
-                match actor { Actor::Human => self.ai_score += 1, Actor::Ai => self.human_score += 1 }
-                ;
-                self.update_match_state();
-                return;
+        // Any swipe before GO is instant loss for that actor (GO not scheduled yet => early)
+        if self.go_ts_ms.is_none_or(|go| ts_ms < go) {
+            let outcome = match actor { Actor::Human => Outcome::EarlyHuman, Actor::Ai => Outcome::EarlyAi };
+            self.forfeit(outcome, ts_ms, self.cfg.draw_cut.then_some(DrawCutStage::Draw), Some((actor, ev)));
+            return;
+        }
+
+        if self.phase == DuelPhase::DrawWindow {
+            // Any direction counts as the draw; only the first one is kept
+            match actor {
+                Actor::Human => if self.human_draw.is_none() { self.human_draw = Some(ev); },
+                Actor::Ai => if self.ai_draw.is_none() { self.ai_draw = Some(ev); },
             }
-        } else {
-            // GO not scheduled yet => early
+            return;
+        }
+        if self.phase == DuelPhase::CutDelay || self.cut_ts_ms.is_some_and(|cut| ts_ms < cut) {
+            // Cutting before the second cue is the cut stage's false start
             let outcome = match actor { Actor::Human => Outcome::EarlyHuman, Actor::Ai => Outcome::EarlyAi };
-            self.round_results.push(RoundResult { 
-                human_opening: self.human_opening, 
-                ai_opening: self.ai_opening, 
-                outcome, 
-                human_reaction_ms: None, 
-                ai_reaction_ms: None 
-            });
-            self.phase = DuelPhase::ResultFlash;
-            self.phase_start_ms = ts_ms;
-            match actor { Actor::Human => self.ai_score += 1, Actor::Ai => self.human_score += 1 }
-            ;
-            self.update_match_state();
+            self.forfeit(outcome, ts_ms, Some(DrawCutStage::Cut), Some((actor, ev)));
             return;
         }
 
         if self.phase != DuelPhase::InputWindow { return; }
-        let go = self.go_ts_ms.unwrap();
-        if ts_ms - go > self.input_window_ms { return; }
-        let ev = SwipeEvent { dir, ts_ms };
+        if self.try_forgive(actor, &ev) { return; }
+        if self.is_combo() {
+            self.on_combo_step(actor, ev);
+            return;
+        }
+        let go = self.input_origin_ms();
+        if ts_ms - go > self.input_window_for(actor) { return; }
         match actor {
             Actor::Human => if self.human_swipe.is_none() { self.human_swipe = Some(ev); },
             Actor::Ai => if self.ai_swipe.is_none() { self.ai_swipe = Some(ev); },
         }
     }
 
+    // Handicap: a side's forgiven wrong input is dropped instead of judged, once per match
+    fn try_forgive(&mut self, actor: Actor, ev: &SwipeEvent) -> bool {
+        if self.actor_done(actor) || ev.ts_ms > self.step_deadline(actor) { return false; }
+        if ev.dir == correct_direction_for(self.next_opening(actor)) { return false; }
+        let (left, forgiven) = match actor {
+            Actor::Human => (&mut self.human_forgives, &mut self.human_forgiven),
+            Actor::Ai => (&mut self.ai_forgives, &mut self.ai_forgiven),
+        };
+        if *left == 0 || forgiven.is_some() { return false; }
+        *left -= 1;
+        *forgiven = Some(ev.clone());
+        true
+    }
+
+    // Forgives each side had when this round started
+    fn round_forgives(&self) -> (u8, u8) {
+        (
+            self.human_forgives + u8::from(self.human_forgiven.is_some()),
+            self.ai_forgives + u8::from(self.ai_forgiven.is_some()),
+        )
+    }
+
+    // Everything a replay needs about the round being played
+    fn snapshot_meta(&self, go_ts_ms: u64) -> RoundMeta {
+        RoundMeta {
+            seed: self.round_seed,
+            human_combo: self.human_combo.clone(),
+            ai_combo: self.ai_combo.clone(),
+            go_ts_ms,
+            human: self.human_swipe.clone(),
+            ai: self.ai_swipe.clone(),
+            human_steps: self.human_steps.clone(),
+            ai_steps: self.ai_steps.clone(),
+            struggle_taps: Vec::new(),
+            struggle_start_ms: None,
+            posture: None,
+            human_draw: self.human_draw.clone(),
+            ai_draw: self.ai_draw.clone(),
+            cut_ts_ms: self.cut_ts_ms,
+            human_forgiven: self.human_forgiven.clone(),
+            ai_forgiven: self.ai_forgiven.clone(),
+            forgives: self.round_forgives(),
+            input_window_ms: self.input_window_ms,
+            tie_window_ms: self.tie_window_ms,
+            go_presented: self.go_presented,
+        }
+    }
+
+    // Round ends on a penalty before any judging: the offender's opponent scores.
+    // `swipe` is the offending input, kept so the round replays.
+    fn forfeit(&mut self, outcome: Outcome, ts_ms: u64, stage: Option<DrawCutStage>, swipe: Option<(Actor, SwipeEvent)>) {
+        // GO may not have fired yet: log the scheduled one so the swipe replays as early
+        let mut meta = self.snapshot_meta(self.go_ts_ms.or(self.delay_target_ms).unwrap_or(ts_ms + 1));
+        meta.posture = self.cfg.posture.then_some(self.posture);
+        match swipe {
+            Some((Actor::Human, ev)) => meta.human = Some(ev),
+            Some((Actor::Ai, ev)) => meta.ai = Some(ev),
+            None => {}
+        }
+        self.round_meta.push(meta);
+        let posture = self.settle_posture(outcome, None, None);
+        let go = self.go_ts_ms;
+        let cue = (self.cue_delay_us(Actor::Human), self.cue_delay_us(Actor::Ai));
+        let draw_ms = |draw: &Option<SwipeEvent>, cue_us: u64| {
+            draw.as_ref().zip(go).map(|(e, go)| ((e.ts_us() - go * 1000).saturating_sub(cue_us) / 1000) as u32)
+        };
+        self.round_results.push(RoundResult {
+            human_opening: self.human_opening,
+            ai_opening: self.ai_opening,
+            outcome,
+            human_reaction_ms: None,
+            ai_reaction_ms: None,
+            struggle_meter: None,
+            posture,
+            human_draw_ms: draw_ms(&self.human_draw, cue.0),
+            ai_draw_ms: draw_ms(&self.ai_draw, cue.1),
+            stage,
+            human_reaction_us: None,
+            ai_reaction_us: None,
+        });
+        self.phase = DuelPhase::ResultFlash;
+        self.phase_start_ms = ts_ms;
+        match outcome {
+            Outcome::EarlyHuman | Outcome::WrongHuman => self.award(Actor::Ai),
+            Outcome::EarlyAi | Outcome::WrongAi => self.award(Actor::Human),
+            _ => {}
+        }
+        self.update_match_state();
+    }
+
+    // Missing the draw forfeits the round; otherwise wait a hidden delay for the cut cue
+    fn finish_draw(&mut self, now_ms: u64) {
+        match (self.human_draw.is_some(), self.ai_draw.is_some()) {
+            (false, true) => self.forfeit(Outcome::WrongHuman, now_ms, Some(DrawCutStage::Draw), None),
+            (true, false) => self.forfeit(Outcome::WrongAi, now_ms, Some(DrawCutStage::Draw), None),
+            (false, false) => self.forfeit(Outcome::Clash, now_ms, Some(DrawCutStage::Draw), None),
+            (true, true) => {
+                let delay = self.rng.range_u64(CUT_DELAY_MIN_MS, CUT_DELAY_MAX_MS);
+                self.phase = DuelPhase::CutDelay;
+                self.phase_start_ms = now_ms;
+                self.delay_target_ms = Some(now_ms + delay);
+            }
+        }
+    }
+
+    fn on_combo_step(&mut self, actor: Actor, ev: SwipeEvent) {
+        if self.actor_done(actor) || ev.ts_ms > self.step_deadline(actor) { return; }
+        let (combo, steps, swipe) = match actor {
+            Actor::Human => (&self.human_combo, &mut self.human_steps, &mut self.human_swipe),
+            Actor::Ai => (&self.ai_combo, &mut self.ai_steps, &mut self.ai_swipe),
+        };
+        let wrong = ev.dir != correct_direction_for(combo[steps.len()]);
+        steps.push(ev.clone());
+        // A wrong step or the final step ends this actor's sequence
+        if wrong || steps.len() == combo.len() { *swipe = Some(ev); }
+    }
+
     fn resolve(&mut self, _now_ms: u64) -> Outcome {
         let go = self.go_ts_ms.unwrap_or(self.phase_start_ms);
+        let origin = self.input_origin_ms();
         let human_dir = self.human_swipe.as_ref().map(|e| e.dir);
         let ai_dir = self.ai_swipe.as_ref().map(|e| e.dir);
-        let human_r = self.human_swipe.as_ref().map(|e| e.ts_ms - go);
-        let ai_r = self.ai_swipe.as_ref().map(|e| e.ts_ms - go);
-        let outcome = judge_outcome(
-            self.human_opening,
-            self.ai_opening,
-            human_dir,
-            ai_dir,
-            human_r,
-            ai_r,
-            TIE_WINDOW_MS,
-        );
+        let human_us = self.human_swipe.as_ref().map(|e| e.ts_us() - origin * 1000);
+        let ai_us = self.ai_swipe.as_ref().map(|e| e.ts_us() - origin * 1000);
+        // Draw-then-cut compares draw + cut; plain rounds just the reaction
+        let draw_us = |draw: &Option<SwipeEvent>| draw.as_ref().map(|e| e.ts_us() - go * 1000);
+        let (human_draw_us, ai_draw_us) = (draw_us(&self.human_draw), draw_us(&self.ai_draw));
+        let human_total = human_us.map(|r| r + human_draw_us.unwrap_or(0));
+        let ai_total = ai_us.map(|r| r + ai_draw_us.unwrap_or(0));
+        // Moving after GO but before its cue reached you is early, not a 0 ms reaction
+        let early = match (self.beat_cue_us(Actor::Human), self.beat_cue_us(Actor::Ai)) {
+            (Some(h), Some(a)) => Some(if a < h { Actor::Ai } else { Actor::Human }),
+            (Some(_), None) => Some(Actor::Human),
+            (None, Some(_)) => Some(Actor::Ai),
+            (None, None) => None,
+        };
+        // Recorded times count from when GO reached each side; the speed check applies it itself
+        let from_cue = |us: Option<u64>, actor| us.filter(|_| self.beat_cue_us(actor).is_none()).map(|us| us.saturating_sub(self.cue_delay_us(actor)));
+        let (human_us, ai_us, human_draw_us, ai_draw_us) = if self.cfg.draw_cut {
+            (human_us, ai_us, from_cue(human_draw_us, Actor::Human), from_cue(ai_draw_us, Actor::Ai))
+        } else {
+            (from_cue(human_us, Actor::Human), from_cue(ai_us, Actor::Ai), human_draw_us, ai_draw_us)
+        };
+        let human_r = human_us.map(|us| us / 1000);
+        let ai_r = ai_us.map(|us| us / 1000);
+        let human_draw_ms = human_draw_us.map(|us| us / 1000);
+        let ai_draw_ms = ai_draw_us.map(|us| us / 1000);
+        let check = self.speed_check();
+        let outcome = if let Some(actor) = early {
+            match actor { Actor::Human => Outcome::EarlyHuman, Actor::Ai => Outcome::EarlyAi }
+        } else if self.is_combo() {
+            judge_combo(
+                &self.human_combo,
+                &self.ai_combo,
+                &self.human_steps,
+                &self.ai_steps,
+                origin,
+                self.cfg.combo_timing,
+                self.tie_window_ms,
+                check,
+            )
+        } else {
+            judge_outcome_with(
+                self.human_opening,
+                self.ai_opening,
+                human_dir,
+                ai_dir,
+                human_total,
+                ai_total,
+                self.tie_window_ms,
+                check,
+            )
+        };
+        let round_posture = self.posture;
+        let posture = self.settle_posture(outcome, human_r, ai_r);
         // Store metadata and result (preallocated capacity prevents allocs during duel)
-        self.round_meta.push(RoundMeta { go_ts_ms: go, human: self.human_swipe.clone(), ai: self.ai_swipe.clone() });
+        self.round_meta.push(self.snapshot_meta(go));
         self.round_results.push(RoundResult {
             human_opening: self.human_opening,
             ai_opening: self.ai_opening,
             outcome,
             human_reaction_ms: human_r.map(|v| v as u32),
             ai_reaction_ms: ai_r.map(|v| v as u32),
+            struggle_meter: None,
+            posture,
+            human_draw_ms: human_draw_ms.map(|v| v as u32),
+            ai_draw_ms: ai_draw_ms.map(|v| v as u32),
+            stage: self.cfg.draw_cut.then_some(if early.is_some() { DrawCutStage::Draw } else { DrawCutStage::Cut }),
+            // Frame-timed inputs keep only their millisecond reaction
+            human_reaction_us: human_us.filter(|_| self.human_swipe.as_ref().is_some_and(SwipeEvent::is_precise)),
+            ai_reaction_us: ai_us.filter(|_| self.ai_swipe.as_ref().is_some_and(SwipeEvent::is_precise)),
         });
+        self.round_meta.last_mut().unwrap().posture = self.cfg.posture.then_some(round_posture);
         outcome
     }
 
-    fn apply_outcome(&mut self, outcome: Outcome) {
+    fn apply_outcome(&mut self, outcome: Outcome, now_ms: u64) {
         match outcome {
-            Outcome::HumanWin | Outcome::WrongAi | Outcome::EarlyAi => self.human_score += 1,
-            Outcome::AiWin | Outcome::WrongHuman | Outcome::EarlyHuman => self.ai_score += 1,
+            Outcome::HumanWin | Outcome::WrongAi | Outcome::EarlyAi => self.award(Actor::Human),
+            Outcome::AiWin | Outcome::WrongHuman | Outcome::EarlyHuman => self.award(Actor::Ai),
+            Outcome::Clash if self.cfg.struggle => {
+                self.open_struggle(now_ms);
+                return;
+            }
             Outcome::Clash => {
-                // Immediate rematch with reduced delay/window
+                // Immediate rematch with reduced delay/window (plain rematch when `clash` is off)
                 let now_ms = self.phase_start_ms;
-                self.enter_random_delay(now_ms, true);
+                self.update_match_state();
+                if self.cfg.reveal == RevealMode::Memory {
+                    self.enter_standoff(now_ms, self.cfg.clash);
+                } else {
+                    self.enter_random_delay(now_ms, self.cfg.clash);
+                }
                 return;
             }
         }
         self.update_match_state();
     }
 
+    /// Enter the sword-lock phase; both sides push the shared meter with `on_struggle_input`.
+    pub fn open_struggle(&mut self, now_ms: u64) {
+        self.phase = DuelPhase::Struggle;
+        self.phase_start_ms = now_ms;
+        self.struggle_meter = 0;
+        self.struggle_taps.clear();
+    }
+
+    pub fn on_struggle_input(&mut self, actor: Actor, ts_ms: u64) {
+        if self.phase != DuelPhase::Struggle { return; }
+        if ts_ms < self.phase_start_ms || ts_ms - self.phase_start_ms >= STRUGGLE_MS { return; }
+        self.struggle_meter += match actor { Actor::Human => 1, Actor::Ai => -1 };
+        self.struggle_taps.push(StruggleTap { actor, ts_ms });
+        if self.struggle_meter.abs() >= STRUGGLE_METER_MAX {
+            self.resolve_struggle(ts_ms);
+        }
+    }
+
+    // A pinned or leading meter takes the round; a dead-even lock stays a clash.
+    // The struggle settles the clash that opened it rather than adding a round.
+    fn resolve_struggle(&mut self, now_ms: u64) {
+        let outcome = match self.struggle_meter {
+            m if m > 0 => Outcome::HumanWin,
+            m if m < 0 => Outcome::AiWin,
+            _ => Outcome::Clash,
+        };
+        let open_clash = self.round_results.last().is_some_and(|rr| rr.outcome == Outcome::Clash && rr.struggle_meter.is_none());
+        if !open_clash {
+            // Struggle opened on its own (replays): log it as a clash with no swipes
+            self.round_meta.push(RoundMeta {
+                human: None,
+                ai: None,
+                human_steps: Vec::new(),
+                ai_steps: Vec::new(),
+                human_draw: None,
+                ai_draw: None,
+                cut_ts_ms: None,
+                human_forgiven: None,
+                ai_forgiven: None,
+                posture: self.cfg.posture.then_some(self.posture),
+                ..self.snapshot_meta(self.phase_start_ms)
+            });
+            self.round_results.push(RoundResult {
+                human_opening: self.human_opening,
+                ai_opening: self.ai_opening,
+                outcome: Outcome::Clash,
+                human_reaction_ms: None,
+                ai_reaction_ms: None,
+                struggle_meter: None,
+                posture: self.cfg.posture.then_some(self.posture),
+                human_draw_ms: None,
+                ai_draw_ms: None,
+                stage: None,
+                human_reaction_us: None,
+                ai_reaction_us: None,
+            });
+        }
+        let meta = self.round_meta.last_mut().unwrap();
+        meta.struggle_taps = self.struggle_taps.clone();
+        meta.struggle_start_ms = Some(self.phase_start_ms);
+        let rr = self.round_results.last_mut().unwrap();
+        rr.outcome = outcome;
+        rr.struggle_meter = Some(self.struggle_meter);
+        match outcome {
+            Outcome::HumanWin => self.award(Actor::Human),
+            Outcome::AiWin => self.award(Actor::Ai),
+            _ => {}
+        }
+        self.update_match_state();
+        self.phase = DuelPhase::ResultFlash;
+        self.phase_start_ms = now_ms;
+    }
+
+    // Scores stop at the byte's cap; the human's full win count is kept apart
+    fn award(&mut self, actor: Actor) {
+        match actor {
+            Actor::Human => {
+                self.human_score = self.human_score.saturating_add(1);
+                self.human_wins += 1;
+            }
+            Actor::Ai => self.ai_score = self.ai_score.saturating_add(1),
+        }
+    }
+
     fn update_match_state(&mut self) {
-        if self.human_score >= ROUNDS_TO_WIN { self.match_state = MatchState::HumanWon; }
-        else if self.ai_score >= ROUNDS_TO_WIN { self.match_state = MatchState::AiWon; }
-        else { self.match_state = MatchState::InProgress; }
+        self.match_state = match self.cfg.format {
+            MatchFormat::FirstTo(n) if self.human_score >= n => MatchState::HumanWon,
+            MatchFormat::FirstTo(n) if self.ai_score >= n => MatchState::AiWon,
+            MatchFormat::Rounds(n) if self.round_results.len() >= n as usize => MatchState::Finished,
+            MatchFormat::Survival if self.ai_score > 0 => MatchState::Finished,
+            _ => MatchState::InProgress,
+        };
     }
 
     // Export last round as a DuelLog for deterministic replay
     pub fn last_duel_log(&self) -> Option<crate::logging::DuelLog> {
-        let i = self.round_results.len().checked_sub(1)?;
-        let rr = &self.round_results[i];
-        let meta = &self.round_meta[i];
+        self.duel_log(self.round_results.len().checked_sub(1)?)
+    }
+
+    /// Export round `i` as a DuelLog; each log carries the RNG state its round started from.
+    pub fn duel_log(&self, i: usize) -> Option<crate::logging::DuelLog> {
+        let rr = self.round_results.get(i)?;
+        let meta = self.round_meta.get(i)?;
         Some(crate::logging::DuelLog {
-            seed: self.seed,
+            seed: meta.seed,
             human_opening: rr.human_opening,
             ai_opening: rr.ai_opening,
-            go: GoEvent { ts_ms: meta.go_ts_ms },
+            go: GoEvent { ts_ms: meta.go_ts_ms, presented: meta.go_presented },
             human: meta.human.clone(),
             ai: meta.ai.clone(),
             outcome: rr.outcome,
+            human_combo: meta.human_combo.clone(),
+            ai_combo: meta.ai_combo.clone(),
+            human_steps: meta.human_steps.clone(),
+            ai_steps: meta.ai_steps.clone(),
+            combo_timing: self.cfg.combo_timing,
+            struggle_taps: meta.struggle_taps.clone(),
+            struggle: rr.struggle_meter.is_some(),
+            struggle_start_ms: meta.struggle_start_ms,
+            posture: meta.posture,
+            posture_penalty: self.cfg.posture_penalty,
+            human_draw: meta.human_draw.clone(),
+            ai_draw: meta.ai_draw.clone(),
+            cut_ts_ms: meta.cut_ts_ms,
+            human_handicap: self.cfg.human_handicap,
+            ai_handicap: self.cfg.ai_handicap,
+            human_forgiven: meta.human_forgiven.clone(),
+            ai_forgiven: meta.ai_forgiven.clone(),
+            human_forgives: meta.forgives.0,
+            ai_forgives: meta.forgives.1,
+            drill: self.cfg.drill.clone(),
+            draw_cut: self.cfg.draw_cut,
+            input_window_ms: Some(meta.input_window_ms),
+            tie_window_ms: Some(meta.tie_window_ms),
+            cue_for_ai: self.cue_for_ai,
+            input_offset_us: self.input_offset_us,
         })
     }
 
+    /// Every round played so far, scored, ready to save or race as a ghost.
+    pub fn match_log(&self) -> crate::logging::MatchLog {
+        let rounds = (0..self.round_results.len()).filter_map(|i| self.duel_log(i)).collect();
+        crate::logging::MatchLog::new(self.seed, rounds, &self.round_results)
+    }
+
     #[cfg(test)]
-    pub fn force_go(&mut self, now_ms: u64) { self.phase = DuelPhase::GoSignal; self.go_ts_ms = Some(now_ms); self.phase_start_ms = now_ms; }
+    pub fn force_go(&mut self, now_ms: u64) { self.phase = DuelPhase::GoSignal; self.go_ts_ms = Some(now_ms); self.go_presented = None; self.phase_start_ms = now_ms; }
 
-    pub fn open_input(&mut self, now_ms: u64) { self.phase = DuelPhase::InputWindow; self.go_ts_ms = Some(now_ms); self.phase_start_ms = now_ms; }
+    pub fn open_input(&mut self, now_ms: u64) {
+        self.phase = DuelPhase::InputWindow;
+        self.go_ts_ms = Some(now_ms);
+        self.go_presented = None;
+        self.phase_start_ms = now_ms;
+        self.human_swipe = None;
+        self.ai_swipe = None;
+        self.human_steps.clear();
+        self.ai_steps.clear();
+        self.human_draw = None;
+        self.ai_draw = None;
+        self.cut_ts_ms = None;
+        self.human_forgiven = None;
+        self.ai_forgiven = None;
+    }
+
+    /// Draw-then-cut counterpart of `open_input`: GO at `now_ms`, draw window open.
+    pub fn open_draw(&mut self, now_ms: u64) {
+        self.open_input(now_ms);
+        self.phase = DuelPhase::DrawWindow;
+    }
+
+    /// Skip the hidden cut delay and give the cut cue at `now_ms` (replays and tests).
+    pub fn open_cut(&mut self, now_ms: u64) {
+        self.phase = DuelPhase::InputWindow;
+        self.cut_ts_ms = Some(now_ms);
+        self.phase_start_ms = now_ms;
+        self.delay_target_ms = None;
+    }
 
     pub fn reset_match(&mut self, now_ms: u64) {
         self.phase = DuelPhase::Standoff;
         self.go_ts_ms = None;
+        self.go_presented = None;
         self.phase_start_ms = now_ms;
         self.delay_target_ms = None;
+        self.pending_decoy = None;
+        self.fired_decoy = None;
         self.human_swipe = None;
         self.ai_swipe = None;
+        self.human_steps.clear();
+        self.ai_steps.clear();
+        self.struggle_meter = 0;
+        self.struggle_taps.clear();
+        self.posture = PostureState::default();
+        self.human_draw = None;
+        self.ai_draw = None;
+        self.cut_ts_ms = None;
+        self.human_forgiven = None;
+        self.ai_forgiven = None;
+        self.human_forgives = u8::from(self.cfg.human_handicap.forgive_wrong);
+        self.ai_forgives = u8::from(self.cfg.ai_handicap.forgive_wrong);
         self.round_results.clear();
         self.round_meta.clear();
         self.match_state = MatchState::InProgress;
         self.human_score = 0;
         self.ai_score = 0;
-        self.input_window_ms = INPUT_WINDOW_MS;
-        self.human_opening = pick_opening(&mut self.rng);
-        self.ai_opening = pick_opening(&mut self.rng);
+        self.human_wins = 0;
+        self.input_window_ms = self.base_window_ms();
+        self.tie_window_ms = self.base_tie_ms();
+        self.standoff_clash = false;
+        self.roll_openings();
+        self.rolled_at_standoff = self.cfg.reveal == RevealMode::Memory;
     }
 }
 
+/// Like `pick_opening`, restricted to `pool` when it is non-empty.
+pub fn pick_opening_from(rng: &mut XorShift32, pool: &[Opening]) -> Opening {
+    if pool.is_empty() { pick_opening(rng) } else { pool[rng.next_u32() as usize % pool.len()] }
+}
+
 pub fn pick_opening(rng: &mut XorShift32) -> Opening {
     match rng.next_u32() % 10 {
         0 => Opening::Up,
@@ -0,0 +1,52 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+// Local save files, one JSON document each
+pub const LAST_MATCH_FILE: &str = "last_match.json";
+pub const BEST_MATCH_FILE: &str = "best_match.json";
+pub const BESTS_FILE: &str = "personal_bests.json";
+pub const DAILY_FILE: &str = "daily.json";
+/// Code plus log of the last finished match, ready to send to a friend.
+pub const CHALLENGE_FILE: &str = "last_challenge.json";
+/// Bracket in progress; rewritten after every match so it can resume.
+pub const TOURNAMENT_FILE: &str = "tournament.json";
+/// Player rating and its history.
+pub const PROFILE_FILE: &str = "profile.json";
+/// Per-device calibration (input latency).
+pub const DEVICE_FILE: &str = "device.json";
+/// Rebound controls (InputBindings); written by hand, never by the game.
+pub const INPUT_MAP_FILE: &str = "input_map.json";
+
+/// Personal records kept across sessions.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PersonalBests {
+    /// Longest survival streak (rounds won before the first loss).
+    #[serde(default)]
+    pub survival_streak: u32,
+}
+
+/// Where local saves live: `IAIDO_SAVE_DIR` if set, else `~/.iaido`, else `./saves`.
+pub fn save_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("IAIDO_SAVE_DIR") {
+        return PathBuf::from(dir);
+    }
+    match std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")) {
+        Some(home) => PathBuf::from(home).join(".iaido"),
+        None => PathBuf::from("saves"),
+    }
+}
+
+pub fn save_json<T: Serialize>(name: &str, value: &T) -> Result<(), String> {
+    let dir = save_dir();
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    fs::write(dir.join(name), json).map_err(|e| e.to_string())
+}
+
+/// Missing or unreadable saves load as `None`.
+pub fn load_json<T: DeserializeOwned>(name: &str) -> Option<T> {
+    let content = fs::read_to_string(save_dir().join(name)).ok()?;
+    serde_json::from_str(&content).ok()
+}
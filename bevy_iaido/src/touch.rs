@@ -1,5 +1,8 @@
 use bevy::prelude::*;
 
+use crate::bindings::InputAction;
+use crate::input_map::ActionInput;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
 pub enum VirtualKey {
     Up, Down, Left, Right, Z, X, S, C, Space, Enter, P
@@ -41,11 +44,11 @@ fn reset_virtual_keys(mut inputs: ResMut<ButtonInput<VirtualKey>>) {
 }
 
 fn toggle_touch_ui(
-    keys: Res<ButtonInput<KeyCode>>,
+    input: ActionInput,
     mut state: ResMut<TouchControlsState>,
     mut q: Query<&mut Visibility, With<TouchControlsRoot>>,
 ) {
-    if keys.just_pressed(KeyCode::KeyT) {
+    if input.just_pressed(InputAction::ToggleTouch) {
         state.enabled = !state.enabled;
         for mut vis in q.iter_mut() {
             *vis = if state.enabled { Visibility::Visible } else { Visibility::Hidden };
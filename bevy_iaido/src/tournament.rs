@@ -0,0 +1,380 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ai::{plan_for_go, plan_for_step, plan_struggle_tap, AiPlan, AiProfile, AiTier};
+use crate::rng::XorShift32;
+use crate::state_machine::{DuelConfig, DuelMachine};
+use crate::types::{Actor, DuelPhase, MatchFormat, MatchState};
+
+pub const MAX_ENTRANTS: usize = 32;
+// Headless matches that somehow never finish are called on score after this much game time
+const SIM_LIMIT_MS: u64 = 600_000;
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum BracketKind {
+    SingleElimination,
+    /// Losers drop to a lower bracket; its winner meets the upper winner in one grand final.
+    DoubleElimination,
+    RoundRobin,
+}
+
+impl std::str::FromStr for BracketKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "single" | "se" => Ok(BracketKind::SingleElimination),
+            "double" | "de" => Ok(BracketKind::DoubleElimination),
+            "roundrobin" | "rr" | "league" => Ok(BracketKind::RoundRobin),
+            _ => Err(format!("unknown bracket kind: {} (single, double, rr)", s)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Entrant {
+    pub name: String,
+    /// `None` for a human hot-seat player.
+    pub ai: Option<AiTier>,
+}
+
+impl Entrant {
+    pub fn human(name: &str) -> Self { Self { name: name.to_string(), ai: None } }
+
+    pub fn ai(tier: AiTier) -> Self { Self { name: format!("{:?} AI", tier), ai: Some(tier) } }
+}
+
+impl std::str::FromStr for Entrant {
+    type Err = String;
+
+    // "ai:<tier>" for an AI entrant, anything else is a human's name
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() { return Err("empty entrant name".to_string()); }
+        match s.strip_prefix("ai:") {
+            Some(tier) => tier.parse().map(Entrant::ai),
+            None => Ok(Entrant::human(s)),
+        }
+    }
+}
+
+/// Where a bracket slot's player comes from.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Source {
+    Entrant(usize),
+    Winner(usize),
+    Loser(usize),
+    Bye,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Bracket {
+    Upper,
+    Lower,
+    GrandFinal,
+    League,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BracketMatch {
+    pub bracket: Bracket,
+    /// 1-based round within its bracket.
+    pub round: u8,
+    pub a: Source,
+    pub b: Source,
+    /// Round wins for (a, b) once played.
+    pub result: Option<(u8, u8)>,
+}
+
+/// Who currently fills a slot.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Slot {
+    /// Waiting on an unplayed match.
+    Pending,
+    Bye,
+    Entrant(usize),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Standing {
+    pub entrant: usize,
+    pub played: u32,
+    pub wins: u32,
+    pub losses: u32,
+    /// Round wins minus round losses, the tie-breaker after match wins.
+    pub round_diff: i32,
+}
+
+/// Bracket state; serialises to JSON so a tournament can resume after a restart.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Tournament {
+    pub kind: BracketKind,
+    /// In seed order: entrant 0 is the top seed.
+    pub entrants: Vec<Entrant>,
+    pub matches: Vec<BracketMatch>,
+    pub seed: u32,
+    /// Every match is `MatchFormat::FirstTo(first_to)`, so it always has a winner.
+    pub first_to: u8,
+}
+
+impl Tournament {
+    pub fn new(kind: BracketKind, entrants: Vec<Entrant>, seed: u32, first_to: u8) -> Result<Self, String> {
+        if !(2..=MAX_ENTRANTS).contains(&entrants.len()) {
+            return Err(format!("a tournament needs 2 to {} entrants, got {}", MAX_ENTRANTS, entrants.len()));
+        }
+        if first_to == 0 { return Err("first_to must be at least 1".to_string()); }
+        let matches = match kind {
+            BracketKind::SingleElimination => elimination(entrants.len(), false),
+            BracketKind::DoubleElimination => elimination(entrants.len(), true),
+            BracketKind::RoundRobin => round_robin(entrants.len()),
+        };
+        Ok(Self { kind, entrants, matches, seed, first_to })
+    }
+
+    pub fn slot(&self, source: Source) -> Slot {
+        match source {
+            Source::Entrant(i) => Slot::Entrant(i),
+            Source::Bye => Slot::Bye,
+            Source::Winner(id) => self.decided(id).map_or(Slot::Pending, |(w, _)| w),
+            Source::Loser(id) => self.decided(id).map_or(Slot::Pending, |(_, l)| l),
+        }
+    }
+
+    /// (winner, loser) once a match is played or settled by a bye.
+    pub fn decided(&self, id: usize) -> Option<(Slot, Slot)> {
+        let m = &self.matches[id];
+        let (a, b) = (self.slot(m.a), self.slot(m.b));
+        if let Some((sa, sb)) = m.result {
+            return Some(if sa > sb { (a, b) } else { (b, a) });
+        }
+        match (a, b) {
+            (Slot::Pending, _) | (_, Slot::Pending) => None,
+            (Slot::Bye, other) | (other, Slot::Bye) => Some((other, Slot::Bye)),
+            _ => None,
+        }
+    }
+
+    pub fn winner(&self, id: usize) -> Option<usize> {
+        match self.decided(id) { Some((Slot::Entrant(i), _)) => Some(i), _ => None }
+    }
+
+    /// Both entrants of an unplayed match, once known.
+    pub fn pairing(&self, id: usize) -> Option<(usize, usize)> {
+        let m = &self.matches[id];
+        if m.result.is_some() { return None; }
+        match (self.slot(m.a), self.slot(m.b)) {
+            (Slot::Entrant(a), Slot::Entrant(b)) => Some((a, b)),
+            _ => None,
+        }
+    }
+
+    /// First match ready to be played, in bracket order.
+    pub fn next_match(&self) -> Option<usize> {
+        (0..self.matches.len()).find(|&id| self.pairing(id).is_some())
+    }
+
+    pub fn report(&mut self, id: usize, a_wins: u8, b_wins: u8) -> Result<(), String> {
+        if self.pairing(id).is_none() { return Err(format!("match {} is not ready to be played", id)); }
+        if a_wins == b_wins { return Err(format!("match {} needs a winner", id)); }
+        self.matches[id].result = Some((a_wins, b_wins));
+        Ok(())
+    }
+
+    pub fn champion(&self) -> Option<usize> {
+        match self.kind {
+            BracketKind::RoundRobin => {
+                let done = self.matches.iter().all(|m| m.result.is_some());
+                done.then(|| self.standings()[0].entrant)
+            }
+            _ => self.winner(self.matches.len() - 1),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool { self.champion().is_some() }
+
+    /// Table of played matches: wins, then round difference, then seed.
+    pub fn standings(&self) -> Vec<Standing> {
+        let mut table: Vec<Standing> = (0..self.entrants.len())
+            .map(|entrant| Standing { entrant, played: 0, wins: 0, losses: 0, round_diff: 0 })
+            .collect();
+        for m in &self.matches {
+            let (Some((sa, sb)), Slot::Entrant(a), Slot::Entrant(b)) = (m.result, self.slot(m.a), self.slot(m.b)) else { continue };
+            for (me, mine, theirs) in [(a, sa, sb), (b, sb, sa)] {
+                let row = &mut table[me];
+                row.played += 1;
+                if mine > theirs { row.wins += 1 } else { row.losses += 1 }
+                row.round_diff += i32::from(mine) - i32::from(theirs);
+            }
+        }
+        table.sort_by(|x, y| y.wins.cmp(&x.wins).then(y.round_diff.cmp(&x.round_diff)).then(x.entrant.cmp(&y.entrant)));
+        table
+    }
+
+    /// Stable per-match seed, so a resumed tournament replays the same openings.
+    pub fn match_seed(&self, id: usize) -> u32 {
+        XorShift32::new(self.seed ^ (id as u32 + 1).wrapping_mul(0x9E37_79B9)).next_u32()
+    }
+
+    pub fn duel_config(&self, id: usize) -> DuelConfig {
+        DuelConfig { seed: self.match_seed(id), format: MatchFormat::FirstTo(self.first_to), ..DuelConfig::default() }
+    }
+
+    /// Play every ready AI-vs-AI match headlessly; returns how many were simulated.
+    pub fn simulate_ai_matches(&mut self) -> usize {
+        let mut played = 0;
+        while let Some((id, a, b)) = (0..self.matches.len()).find_map(|id| {
+            let (a, b) = self.pairing(id)?;
+            Some((id, self.entrants[a].ai?, self.entrants[b].ai?))
+        }) {
+            let dm = simulate_match(self.duel_config(id), a.profile(), b.profile());
+            // A stalled match goes to the higher seed (side a) on equal score
+            let (sa, sb) = if dm.human_score == dm.ai_score { (dm.human_score + 1, dm.ai_score) } else { (dm.human_score, dm.ai_score) };
+            self.matches[id].result = Some((sa, sb));
+            played += 1;
+        }
+        played
+    }
+}
+
+fn push(matches: &mut Vec<BracketMatch>, bracket: Bracket, round: u8, a: Source, b: Source) -> usize {
+    matches.push(BracketMatch { bracket, round, a, b, result: None });
+    matches.len() - 1
+}
+
+// Standard seeding order for a power-of-two bracket: 1v8, 4v5, 2v7, 3v6, ...
+fn seed_order(size: usize) -> Vec<usize> {
+    let mut order = vec![0];
+    while order.len() < size {
+        let n = order.len() * 2;
+        order = order.iter().flat_map(|&s| [s, n - 1 - s]).collect();
+    }
+    order
+}
+
+fn elimination(entrants: usize, double: bool) -> Vec<BracketMatch> {
+    let size = entrants.next_power_of_two();
+    let order = seed_order(size);
+    let seat = |s: usize| if s < entrants { Source::Entrant(s) } else { Source::Bye };
+    let mut matches = Vec::new();
+
+    // Upper bracket, one Vec of match ids per round
+    let mut upper: Vec<Vec<usize>> = Vec::new();
+    let mut sources: Vec<Source> = order.into_iter().map(seat).collect();
+    let mut round = 1;
+    while sources.len() > 1 {
+        let ids: Vec<usize> = sources.chunks(2).map(|p| push(&mut matches, Bracket::Upper, round, p[0], p[1])).collect();
+        sources = ids.iter().map(|&id| Source::Winner(id)).collect();
+        upper.push(ids);
+        round += 1;
+    }
+    if !double { return matches; }
+
+    // Lower bracket: pair off first-round losers, then alternate drop-in rounds
+    // (lower winners vs the next upper round's losers) with plain halving rounds
+    let upper_final = *upper.last().unwrap().first().unwrap();
+    let lower_champ = if upper.len() == 1 {
+        Source::Loser(upper_final)
+    } else {
+        let mut round = 1;
+        let mut cur: Vec<usize> = upper[0].chunks(2)
+            .map(|p| push(&mut matches, Bracket::Lower, round, Source::Loser(p[0]), Source::Loser(p[1])))
+            .collect();
+        for ids in &upper[1..] {
+            round += 1;
+            // Reversed so early rematches are less likely
+            cur = cur.iter().zip(ids.iter().rev())
+                .map(|(&l, &u)| push(&mut matches, Bracket::Lower, round, Source::Winner(l), Source::Loser(u)))
+                .collect();
+            if cur.len() > 1 {
+                round += 1;
+                cur = cur.chunks(2)
+                    .map(|p| push(&mut matches, Bracket::Lower, round, Source::Winner(p[0]), Source::Winner(p[1])))
+                    .collect();
+            }
+        }
+        Source::Winner(cur[0])
+    };
+    push(&mut matches, Bracket::GrandFinal, 1, Source::Winner(upper_final), lower_champ);
+    matches
+}
+
+// Circle method: every entrant meets every other once, spread over rounds
+fn round_robin(entrants: usize) -> Vec<BracketMatch> {
+    let n = entrants + entrants % 2;
+    let mut seats: Vec<usize> = (0..n).collect();
+    let mut matches = Vec::new();
+    for round in 0..n - 1 {
+        for i in 0..n / 2 {
+            let (a, b) = (seats[i], seats[n - 1 - i]);
+            if a < entrants && b < entrants {
+                let (a, b) = (a.min(b), a.max(b));
+                matches.push(BracketMatch {
+                    bracket: Bracket::League,
+                    round: round as u8 + 1,
+                    a: Source::Entrant(a),
+                    b: Source::Entrant(b),
+                    result: None,
+                });
+            }
+        }
+        seats[1..].rotate_right(1);
+    }
+    matches
+}
+
+// Drives one side of a headless match the way the Bevy AI does
+struct Bot {
+    actor: Actor,
+    profile: AiProfile,
+    rng: XorShift32,
+    plan: Option<AiPlan>,
+    next_tap_ms: Option<u64>,
+}
+
+impl Bot {
+    fn drive(&mut self, dm: &mut DuelMachine, now_ms: u64) {
+        if dm.phase == DuelPhase::Struggle {
+            let next = *self.next_tap_ms.get_or_insert_with(|| now_ms + plan_struggle_tap(self.profile, &mut self.rng));
+            if now_ms >= next {
+                dm.on_struggle_input(self.actor, now_ms);
+                self.next_tap_ms = Some(now_ms + plan_struggle_tap(self.profile, &mut self.rng));
+            }
+            return;
+        }
+        self.next_tap_ms = None;
+        let done = match self.actor { Actor::Human => dm.human_swipe.is_some(), Actor::Ai => dm.ai_swipe.is_some() };
+        let Some(go) = dm.go_ts_ms.filter(|_| !done && !dm.awaiting_cut(self.actor)) else {
+            self.plan = None;
+            return;
+        };
+        let origin = dm.cut_ts_ms.unwrap_or(go);
+        let last_step = dm.steps(self.actor).last().map(|e| e.ts_ms);
+        let (profile, rng) = (self.profile, &mut self.rng);
+        let plan = self.plan.get_or_insert_with(|| match last_step {
+            Some(_) => plan_for_step(profile, rng),
+            None => plan_for_go(profile, rng),
+        });
+        if now_ms >= last_step.unwrap_or(origin) + plan.reaction_ms {
+            let dir = plan.decide_dir(dm.next_opening(self.actor), self.rng);
+            dm.on_swipe(self.actor, dir, now_ms);
+            self.plan = None;
+        }
+    }
+}
+
+/// Run a full match between two AI profiles without a renderer, in 1 ms steps.
+/// `a` plays the human side, `b` the AI side.
+pub fn simulate_match(cfg: DuelConfig, a: AiProfile, b: AiProfile) -> DuelMachine {
+    let seed = cfg.seed;
+    let mut dm = DuelMachine::new(cfg, 0);
+    let mut bots = [
+        Bot { actor: Actor::Human, profile: a, rng: XorShift32::new(seed ^ 0xA11C_E5ED), plan: None, next_tap_ms: None },
+        Bot { actor: Actor::Ai, profile: b, rng: XorShift32::new(seed ^ 0xDEAD_BEEF), plan: None, next_tap_ms: None },
+    ];
+    let mut now_ms = 0;
+    while dm.match_state == MatchState::InProgress && now_ms < SIM_LIMIT_MS {
+        dm.tick(now_ms);
+        for bot in &mut bots { bot.drive(&mut dm, now_ms); }
+        now_ms += 1;
+    }
+    dm
+}
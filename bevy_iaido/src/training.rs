@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{Opening, Outcome, RoundResult};
+
+/// Running tally for one drilled opening.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DrillStats {
+    pub opening: Opening,
+    pub attempts: u32,
+    pub hits: u32,
+    /// Sum of reaction times over hits.
+    pub total_ms: u32,
+    pub best_ms: Option<u32>,
+}
+
+impl DrillStats {
+    pub fn avg_ms(&self) -> Option<u32> {
+        (self.hits > 0).then(|| self.total_ms / self.hits)
+    }
+
+    /// Hit rate in percent.
+    pub fn accuracy(&self) -> u32 {
+        (self.hits * 100).checked_div(self.attempts).unwrap_or(0)
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TrainingReport {
+    /// One entry per opening seen, in order of first appearance.
+    pub drills: Vec<DrillStats>,
+}
+
+impl TrainingReport {
+    /// Drills ordered slowest average first; openings never hit come first of all.
+    pub fn slowest(&self) -> Vec<&DrillStats> {
+        let mut v: Vec<&DrillStats> = self.drills.iter().collect();
+        v.sort_by_key(|d| std::cmp::Reverse(d.avg_ms().unwrap_or(u32::MAX)));
+        v
+    }
+
+    pub fn hits(&self) -> u32 { self.drills.iter().map(|d| d.hits).sum() }
+    pub fn attempts(&self) -> u32 { self.drills.iter().map(|d| d.attempts).sum() }
+}
+
+/// A round counts as a hit when the human answered in time without a false start
+/// or wrong direction; with no opponent that is simply `HumanWin`.
+pub fn is_hit(result: &RoundResult) -> bool {
+    result.human_reaction_ms.is_some() && !matches!(result.outcome, Outcome::WrongHuman | Outcome::EarlyHuman)
+}
+
+pub fn training_report(results: &[RoundResult]) -> TrainingReport {
+    let mut report = TrainingReport::default();
+    for r in results {
+        let i = match report.drills.iter().position(|d| d.opening == r.human_opening) {
+            Some(i) => i,
+            None => {
+                report.drills.push(DrillStats { opening: r.human_opening, attempts: 0, hits: 0, total_ms: 0, best_ms: None });
+                report.drills.len() - 1
+            }
+        };
+        let d = &mut report.drills[i];
+        d.attempts += 1;
+        if is_hit(r) {
+            let ms = r.human_reaction_ms.unwrap_or(0);
+            d.hits += 1;
+            d.total_ms += ms;
+            d.best_ms = Some(d.best_ms.map_or(ms, |b| b.min(ms)));
+        }
+    }
+    report
+}
@@ -28,15 +28,19 @@ pub enum DuelPhase {
     Standoff,
     RandomDelay,
     GoSignal,
+    DrawWindow,
+    CutDelay,
+    CutSignal,
     InputWindow,
     Resolution,
+    Struggle,
     ResultFlash,
     NextRound,
     Finished,
 }
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
-pub enum MatchState { InProgress, HumanWon, AiWon }
+pub enum MatchState { InProgress, HumanWon, AiWon, Finished }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct RoundResult {
@@ -45,20 +49,197 @@ pub struct RoundResult {
     pub outcome: Outcome,
     pub human_reaction_ms: Option<u32>,
     pub ai_reaction_ms: Option<u32>,
+    /// Final meter of a sword-lock struggle (positive favours the human).
+    #[serde(default)]
+    pub struggle_meter: Option<i32>,
+    /// Posture after this round when the posture system is on.
+    #[serde(default)]
+    pub posture: Option<PostureState>,
+    /// Draw-then-cut rounds: draw reaction times and the stage a penalty came from.
+    #[serde(default)]
+    pub human_draw_ms: Option<u32>,
+    #[serde(default)]
+    pub ai_draw_ms: Option<u32>,
+    #[serde(default)]
+    pub stage: Option<DrawCutStage>,
+    /// Reaction times at full precision; `*_reaction_ms` is these truncated.
+    #[serde(default)]
+    pub human_reaction_us: Option<u64>,
+    #[serde(default)]
+    pub ai_reaction_us: Option<u64>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct RoundMeta {
+    /// RNG state before this round's openings were picked; seeds its replay.
+    pub seed: u32,
+    pub human_combo: Vec<Opening>,
+    pub ai_combo: Vec<Opening>,
     pub go_ts_ms: u64,
     pub human: Option<SwipeEvent>,
     pub ai: Option<SwipeEvent>,
+    pub human_steps: Vec<SwipeEvent>,
+    pub ai_steps: Vec<SwipeEvent>,
+    pub struggle_taps: Vec<StruggleTap>,
+    /// When a clash went into a sword-lock, the struggle began here.
+    #[serde(default)]
+    pub struggle_start_ms: Option<u64>,
+    /// Posture in effect while this round was played.
+    pub posture: Option<PostureState>,
+    pub human_draw: Option<SwipeEvent>,
+    pub ai_draw: Option<SwipeEvent>,
+    pub cut_ts_ms: Option<u64>,
+    /// Wrong inputs dropped by a handicap, and the forgives each side had at round start.
+    pub human_forgiven: Option<SwipeEvent>,
+    pub ai_forgiven: Option<SwipeEvent>,
+    pub forgives: (u8, u8),
+    /// Timing in effect (survival tightens both from round to round).
+    pub input_window_ms: u64,
+    pub tie_window_ms: u64,
+    #[serde(default)]
+    pub go_presented: Option<GoPresentation>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
-pub struct GoEvent { pub ts_ms: u64 }
+pub struct GoEvent {
+    pub ts_ms: u64,
+    /// When the cue reached the player; older logs and headless runs have none.
+    #[serde(default)]
+    pub presented: Option<GoPresentation>,
+}
+
+/// The frame that showed GO, plus how far the GO sound trails it.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GoPresentation {
+    pub presented_us: u64,
+    pub audio_latency_ms: u32,
+}
+
+impl GoPresentation {
+    /// Moment reactions are measured from.
+    pub fn cue_us(&self) -> u64 { self.presented_us + self.audio_latency_ms as u64 * 1000 }
+}
+
+/// `frac_us` (0..1000) carries the sub-millisecond part of the input's own timestamp;
+/// `None` when the input only had its frame's time, good to the millisecond at best.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SwipeEvent {
+    pub dir: Direction,
+    pub ts_ms: u64,
+    #[serde(default)]
+    pub frac_us: Option<u32>,
+}
+
+impl SwipeEvent {
+    pub fn at_us(dir: Direction, ts_us: u64) -> Self {
+        Self { dir, ts_ms: ts_us / 1000, frac_us: Some((ts_us % 1000) as u32) }
+    }
+
+    pub fn at_ms(dir: Direction, ts_ms: u64) -> Self { Self { dir, ts_ms, frac_us: None } }
+
+    pub fn ts_us(&self) -> u64 { self.ts_ms * 1000 + self.frac_us.unwrap_or(0) as u64 }
+
+    /// Whether the timestamp came from the input itself rather than the frame.
+    pub fn is_precise(&self) -> bool { self.frac_us.is_some() }
+}
+
+// When the human's opening is shown relative to GO
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RevealMode {
+    #[default]
+    Always,
+    AtGo,
+    Memory,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StruggleTap { pub actor: Actor, pub ts_ms: u64 }
+
+// Draw-then-cut rounds: GO opens the draw (any swipe), a second cue demands the cut
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DrawCutStage { Draw, Cut }
+
+// How a match ends: first side to N round wins, or a fixed-length session with no winner
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum MatchFormat {
+    FirstTo(u8),
+    Rounds(u16),
+    /// One life: endless rounds until the human loses one, tightening per `SurvivalSchedule`.
+    Survival,
+}
+
+/// How survival tightens with each round survived; defaults in `config` (SURVIVAL_*).
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SurvivalSchedule {
+    pub window_start_ms: u64,
+    pub window_step_ms: u64,
+    pub window_min_ms: u64,
+    pub tie_start_ms: u64,
+    pub tie_step_ms: u64,
+    pub tie_min_ms: u64,
+    /// Taken off the AI's mean reaction per round, down to `ai_min_reaction_ms`.
+    pub ai_step_ms: u64,
+    pub ai_min_reaction_ms: u64,
+}
+
+impl SurvivalSchedule {
+    pub fn window_at(&self, round: u32) -> u64 {
+        self.window_start_ms.saturating_sub(self.window_step_ms * round as u64).max(self.window_min_ms)
+    }
+
+    pub fn tie_at(&self, round: u32) -> u64 {
+        self.tie_start_ms.saturating_sub(self.tie_step_ms * round as u64).max(self.tie_min_ms)
+    }
+
+    /// AI mean reaction for `round`; never slower than `base_ms`.
+    pub fn ai_reaction_at(&self, base_ms: u64, round: u32) -> u64 {
+        base_ms.saturating_sub(self.ai_step_ms * round as u64).max(self.ai_min_reaction_ms.min(base_ms))
+    }
+}
+
+// Per-side handicap so mismatched players can duel fairly; all zero/off by default
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Handicap {
+    /// Added to this side's input window.
+    pub extra_window_ms: u64,
+    /// Subtracted from this side's reaction time in the speed check.
+    pub reaction_offset_ms: u64,
+    /// Extra tie tolerance: the opponent must win by this much more.
+    pub extra_tie_ms: u64,
+    /// Drop this side's first wrong input of the match instead of judging it.
+    pub forgive_wrong: bool,
+}
+
+// Posture carried across rounds; a broken side is penalised for the following round
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PostureState {
+    pub human: u8,
+    pub ai: u8,
+    pub human_broken: bool,
+    pub ai_broken: bool,
+}
+
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PosturePenalty {
+    #[default]
+    ShrinkWindow,
+    WidenOpponentTie,
+}
+
+// How multi-strike combo rounds compare two completed sequences
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ComboTiming {
+    #[default]
+    Total,
+    PerStep,
+}
+
+// False GO cues fired during RandomDelay when the decoy mutator is on
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DecoyKind { Flash, Sound, Twitch }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
-pub struct SwipeEvent { pub dir: Direction, pub ts_ms: u64 }
+pub struct DecoyEvent { pub kind: DecoyKind, pub ts_ms: u64 }
 
 impl fmt::Display for Direction {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -78,4 +259,20 @@ impl fmt::Display for Direction {
     }
 }
 
+impl std::str::FromStr for Opening {
+    type Err = String;
+
+    // Accepts the variant name in any case, e.g. "upright" or "UpDown"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let all = [
+            Opening::Up, Opening::Down, Opening::Left, Opening::Right,
+            Opening::UpLeft, Opening::UpRight, Opening::DownLeft, Opening::DownRight,
+            Opening::UpDown, Opening::LeftRight,
+        ];
+        all.into_iter()
+            .find(|o| format!("{:?}", o).eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("unknown opening: {}", s))
+    }
+}
+
 pub fn dur_ms(d: Duration) -> u64 { d.as_millis() as u64 }
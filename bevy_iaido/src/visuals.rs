@@ -8,12 +8,12 @@ use std::path::{Path, PathBuf};
 use std::time::Duration;
 use rand::Rng;
 
-use crate::{Actor, AttackCue, ClashCue, GoCue, SlashCue, InputDetected, DebugInputCue};
+use crate::{Actor, AttackCue, ClashCue, DecoyCue, DecoyKind, GoCue, SlashCue, InputDetected, DebugInputCue};
 use crate::types::Direction as GameDirection;
 use crate::plugin::{DuelRuntime, DebugState, AnimationEditMode};
 use crate::combat::correct_direction_for;
-#[cfg(feature = "bevy")]
-use crate::touch::VirtualKey;
+use crate::bindings::InputAction;
+use crate::input_map::ActionInput;
 use bevy::window::PrimaryWindow;
 
 pub struct VisualsPlugin;
@@ -101,6 +101,7 @@ impl Plugin for VisualsPlugin {
             .add_systems(PostStartup, setup_characters)
             .add_systems(Update, (
                 handle_go_cue,
+                handle_decoy_cue,
                 handle_slash_cue,
                 handle_clash_cue,
                 apply_camera_shake,
@@ -168,6 +169,13 @@ fn update_character_stance(
                 if let Some(swipe) = &rt.machine.ai_swipe {
                     (swipe.dir, true)
                 } else {
+                    // The AI stance telegraphs the human's opening; hold idle while it is hidden
+                    let now_ms = (time.elapsed_seconds_f64() * 1000.0) as u64;
+                    if !rt.machine.opening_visible(now_ms) {
+                        frame_idx.index = frames.index_for_name(AI_IDLE_FRAME).unwrap_or(0);
+                        apply_frame(frames, &mut frame_idx, &mut texture);
+                        continue;
+                    }
                     (correct_direction_for(rt.machine.human_opening), false)
                 }
             }
@@ -207,8 +215,7 @@ struct AnimationEvents<'w> {
 fn animation_tester(
     mut char_q: Query<(Entity, &Character, &mut FrameIndex, &mut Handle<Image>), Without<DeathRespawn>>,
     mut move_q: Query<(&Character, &mut Transform, &mut OriginalTransform)>,
-    keys: Res<ButtonInput<KeyCode>>,
-    vkeys: Res<ButtonInput<VirtualKey>>,
+    input: ActionInput,
     debug_state: Res<DebugState>,
     events: AnimationEvents,
     mut controller_state: ResMut<CharacterControllerState>,
@@ -226,8 +233,8 @@ fn animation_tester(
 
     if edit_mode.0 {
         let mut delta = 0;
-        if keys.just_pressed(KeyCode::ArrowLeft) || vkeys.just_pressed(VirtualKey::Left) { delta = -1; }
-        if keys.just_pressed(KeyCode::ArrowRight) || vkeys.just_pressed(VirtualKey::Right) { delta = 1; }
+        if input.just_pressed(InputAction::MoveLeft) { delta = -1; }
+        if input.just_pressed(InputAction::MoveRight) { delta = 1; }
 
         if delta != 0 {
             for (_entity, character, mut frame_idx, mut texture) in char_q.iter_mut() {
@@ -244,23 +251,23 @@ fn animation_tester(
     }
 
     if edit_mode.0 {
-        if keys.just_pressed(KeyCode::Space) || vkeys.just_pressed(VirtualKey::Space) {
+        if input.just_pressed(InputAction::Dash) {
             if let Some(idx) = current_human_index(&mut char_q) {
                 controller_state.controller.slash_index = idx;
             }
             slash_tx.send(SlashCue { actor: Actor::Human });
         }
-        if keys.just_pressed(KeyCode::Enter) || vkeys.just_pressed(VirtualKey::Enter) {
+        if input.just_pressed(InputAction::SetClash) {
             if let Some(idx) = current_human_index(&mut char_q) {
                 controller_state.controller.clash_index = idx;
             }
             clash_tx.send(ClashCue);
         }
-    } else if keys.just_pressed(KeyCode::Space) || vkeys.just_pressed(VirtualKey::Space) {
+    } else if input.just_pressed(InputAction::Dash) {
         debug_input_tx.send(DebugInputCue { actor: Actor::Human, label: "SPACE DASH".to_string() });
         dash_forward(&mut move_q);
     }
-    if (keys.just_pressed(KeyCode::KeyX) || vkeys.just_pressed(VirtualKey::X)) && (keys.pressed(KeyCode::KeyS) || vkeys.pressed(VirtualKey::S)) {
+    if (input.just_pressed(InputAction::HeavySlash)) && (input.pressed(InputAction::Spin)) {
         debug_input_tx.send(DebugInputCue { actor: Actor::Human, label: "S+X DOWN".to_string() });
         if let Some(seq) = frames.human.sequence_indices(&SX_FRAMES) {
             if let Some(return_idx) = current_human_index(&mut char_q) {
@@ -280,7 +287,7 @@ fn animation_tester(
             println!("Missing one or more top slash heavy frames.");
         }
         controller_state.x_armed = false;
-    } else if keys.just_pressed(KeyCode::KeyX) || vkeys.just_pressed(VirtualKey::X) {
+    } else if input.just_pressed(InputAction::HeavySlash) {
         debug_input_tx.send(DebugInputCue { actor: Actor::Human, label: "X DOWN".to_string() });
         if let Some(idx) = frames.human.index_for_name(X_PRESS_FRAME) {
             play_frame(Actor::Human, idx, &frames, &mut char_q, &mut commands);
@@ -291,7 +298,7 @@ fn animation_tester(
             println!("Missing frame: {}", X_PRESS_FRAME);
         }
     }
-    if (keys.just_pressed(KeyCode::KeyZ) || vkeys.just_pressed(VirtualKey::Z)) && parry_state.ready {
+    if (input.just_pressed(InputAction::Slash)) && parry_state.ready {
         debug_input_tx.send(DebugInputCue { actor: Actor::Human, label: "PARRY COUNTER".to_string() });
         if let Some(idx) = frames.human.index_for_name(PARRY_COUNTER_FRAME) {
             play_frame(Actor::Human, idx, &frames, &mut char_q, &mut commands);
@@ -302,7 +309,7 @@ fn animation_tester(
         }
         parry_state.ready = false;
         controller_state.z_up_armed = false;
-    } else if keys.just_pressed(KeyCode::KeyZ) || vkeys.just_pressed(VirtualKey::Z) {
+    } else if input.just_pressed(InputAction::Slash) {
         debug_input_tx.send(DebugInputCue { actor: Actor::Human, label: "Z DOWN".to_string() });
         if let Some(idx) = frames.human.index_for_name(Z_PRESS_FRAME) {
             play_frame(Actor::Human, idx, &frames, &mut char_q, &mut commands);
@@ -313,7 +320,7 @@ fn animation_tester(
             println!("Missing frame: {}", Z_PRESS_FRAME);
         }
     }
-    if (keys.just_released(KeyCode::KeyZ) || vkeys.just_released(VirtualKey::Z)) && controller_state.z_up_armed {
+    if (input.just_released(InputAction::Slash)) && controller_state.z_up_armed {
         debug_input_tx.send(DebugInputCue { actor: Actor::Human, label: "Z UP".to_string() });
         if let Some(idx) = frames.human.index_for_name(Z_RELEASE_FRAME) {
             play_frame(Actor::Human, idx, &frames, &mut char_q, &mut commands);
@@ -324,7 +331,7 @@ fn animation_tester(
         }
         controller_state.z_up_armed = false;
     }
-    if (keys.just_released(KeyCode::KeyX) || vkeys.just_released(VirtualKey::X)) && controller_state.x_armed {
+    if (input.just_released(InputAction::HeavySlash)) && controller_state.x_armed {
         debug_input_tx.send(DebugInputCue { actor: Actor::Human, label: "X UP".to_string() });
         if let Some(seq) = frames.human.sequence_indices(&[X_RELEASE_FRAME, X_FOLLOW_FRAME]) {
             play_sequence(Actor::Human, seq, &frames, &mut char_q, &mut commands);
@@ -336,7 +343,7 @@ fn animation_tester(
         }
         controller_state.x_armed = false;
     }
-    if keys.just_pressed(KeyCode::KeyS) || vkeys.just_pressed(VirtualKey::S) {
+    if input.just_pressed(InputAction::Spin) {
         debug_input_tx.send(DebugInputCue { actor: Actor::Human, label: "S DOWN".to_string() });
         let now_ms = (time.elapsed_seconds_f64() * 1000.0) as u64;
         let is_double = now_ms.saturating_sub(controller_state.s_last_press_ms) <= S_DOUBLE_WINDOW_MS;
@@ -382,7 +389,7 @@ fn animation_tester(
             println!("Missing frame: {}", S_PRESS_FRAME);
         }
     }
-    if (keys.just_released(KeyCode::KeyS) || vkeys.just_released(VirtualKey::S)) && controller_state.s_waiting_release && !controller_state.s_double_active {
+    if (input.just_released(InputAction::Spin)) && controller_state.s_waiting_release && !controller_state.s_double_active {
         debug_input_tx.send(DebugInputCue { actor: Actor::Human, label: "S UP".to_string() });
         if let Some(seq) = frames.human.sequence_indices(&S_RELEASE_FRAMES) {
             if let Some(return_idx) = frames.human.index_for_name(S_PRESS_FRAME) {
@@ -405,10 +412,10 @@ fn animation_tester(
         }
         controller_state.s_waiting_release = false;
     }
-    if keys.just_pressed(KeyCode::KeyC) || vkeys.just_pressed(VirtualKey::C) {
+    if input.just_pressed(InputAction::Block) {
         let now_ms = (time.elapsed_seconds_f64() * 1000.0) as u64;
         block_state.human_last_ms = now_ms;
-        if keys.pressed(KeyCode::ArrowLeft) || vkeys.pressed(VirtualKey::Left) {
+        if input.pressed(InputAction::MoveLeft) {
             debug_input_tx.send(DebugInputCue { actor: Actor::Human, label: "C+LEFT".to_string() });
             if let Some(idx) = frames.human.index_for_name(BACK_HEAVY_FRAME) {
                 play_frame_with_duration(
@@ -423,7 +430,7 @@ fn animation_tester(
                 println!("Missing frame: {}", BACK_HEAVY_FRAME);
             }
             controller_state.block_hold_active = false;
-        } else if keys.pressed(KeyCode::ArrowDown) || vkeys.pressed(VirtualKey::Down) {
+        } else if input.pressed(InputAction::MoveDown) {
             debug_input_tx.send(DebugInputCue { actor: Actor::Human, label: "C+DOWN".to_string() });
             if let Some(idx) = frames.human.index_for_name(BLOCK_DOWN_FRAME) {
                 play_frame_with_duration(
@@ -457,11 +464,11 @@ fn animation_tester(
             }
         }
     }
-    if keys.just_released(KeyCode::KeyC) || vkeys.just_released(VirtualKey::C) {
+    if input.just_released(InputAction::Block) {
         debug_input_tx.send(DebugInputCue { actor: Actor::Human, label: "C UP".to_string() });
         controller_state.block_hold_active = false;
     }
-    if keys.just_pressed(KeyCode::KeyP) || vkeys.just_pressed(VirtualKey::P) {
+    if input.just_pressed(InputAction::SaveController) {
         if let Err(err) = save_controller(&controller_state.controller_path, &controller_state.controller) {
             println!("Failed to save controller: {}", err);
         } else {
@@ -827,6 +834,34 @@ fn handle_go_cue(
     }
 }
 
+// Opponent twitch: a short feint toward the player that snaps back
+fn handle_decoy_cue(
+    mut decoy_rx: EventReader<DecoyCue>,
+    mut char_q: Query<(&Character, &OriginalTransform, &mut Animator<Transform>), Without<DeathRespawn>>,
+    debug_state: Res<DebugState>,
+) {
+    if matches!(*debug_state, DebugState::Animation) { return; }
+    for ev in decoy_rx.read() {
+        if ev.kind != DecoyKind::Twitch { continue; }
+        for (character, original, mut animator) in char_q.iter_mut() {
+            if !matches!(character.actor, Actor::Ai) { continue; }
+            let start_pos = original.0;
+            let end_pos = start_pos + Vec3::new(-24.0, 0.0, 0.0);
+            let feint = Tween::new(
+                EaseMethod::EaseFunction(EaseFunction::QuadraticOut),
+                Duration::from_millis(60),
+                TransformPositionLens { start: start_pos, end: end_pos },
+            );
+            let ret = Tween::new(
+                EaseMethod::EaseFunction(EaseFunction::QuadraticIn),
+                Duration::from_millis(90),
+                TransformPositionLens { start: end_pos, end: start_pos },
+            );
+            animator.set_tweenable(feint.then(ret));
+        }
+    }
+}
+
 fn handle_slash_cue(
     mut commands: Commands,
     mut slash_rx: EventReader<SlashCue>,
@@ -1487,7 +1522,7 @@ fn play_sequence_no_return(
 
 fn update_block_hold(
     time: Res<Time>,
-    keys: Res<ButtonInput<KeyCode>>,
+    input: ActionInput,
     debug_state: Res<DebugState>,
     mut controller_state: ResMut<CharacterControllerState>,
     frames: Res<FrameLibrary>,
@@ -1498,7 +1533,7 @@ fn update_block_hold(
     if !controller_state.block_hold_active {
         return;
     }
-    if !keys.pressed(KeyCode::KeyC) {
+    if !input.pressed(InputAction::Block) {
         controller_state.block_hold_active = false;
         return;
     }
@@ -1976,8 +2011,7 @@ fn stagger_target(
 
 fn update_walk_input(
     time: Res<Time>,
-    keys: Res<ButtonInput<KeyCode>>,
-    vkeys: Res<ButtonInput<VirtualKey>>,
+    input: ActionInput,
     debug_state: Res<DebugState>,
     edit_mode: Res<AnimationEditMode>,
     mut move_intent: ResMut<MoveIntent>,
@@ -1989,8 +2023,8 @@ fn update_walk_input(
         return;
     }
     let mut dir: f32 = 0.0;
-    if keys.pressed(KeyCode::ArrowRight) || vkeys.pressed(VirtualKey::Right) { dir += 1.0; }
-    if keys.pressed(KeyCode::ArrowLeft) || vkeys.pressed(VirtualKey::Left) { dir -= 1.0; }
+    if input.pressed(InputAction::MoveRight) { dir += 1.0; }
+    if input.pressed(InputAction::MoveLeft) { dir -= 1.0; }
     move_intent.dir = dir;
     if dir.abs() < f32::EPSILON { return; }
     let speed = 240.0;